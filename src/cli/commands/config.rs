@@ -0,0 +1,26 @@
+use clap::Subcommand;
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    #[command(about = "Print the value of a dotted key, e.g. defaults.groupId")]
+    Get {
+        key: String,
+    },
+
+    #[command(about = "Set a dotted key, e.g. 'xspring config set defaults.javaVersion 21'")]
+    Set {
+        key: String,
+        value: String,
+    },
+
+    #[command(about = "Remove a dotted key from the config file")]
+    Unset {
+        key: String,
+    },
+
+    #[command(about = "Print every configured value")]
+    List,
+
+    #[command(about = "Open the config file in $VISUAL or $EDITOR")]
+    Edit,
+}
@@ -1,4 +1,10 @@
+pub mod config;
+pub mod preset;
+pub mod project;
+
+use std::path::PathBuf;
 use clap::{ArgAction, ArgGroup, Subcommand};
+use crate::cli::commands::{config::ConfigCommand, preset::PresetCommand, project::ProjectArgs};
 
 #[derive(Subcommand, Debug)]
 pub enum Commands{
@@ -11,6 +17,62 @@ pub enum Commands{
 
         #[arg(short = 'd', long, action = ArgAction::SetTrue)]
         deps: bool,
+
+        #[arg(short = 'r', long, action = ArgAction::SetTrue, help = "Review and edit every value before generating")]
+        review: bool,
+    },
+
+    #[command(about = "Configure the project on a single full-screen form, like start.spring.io")]
+    Tui,
+
+    #[command(about = "Generate a project without any prompts, using flags and the Initializr defaults")]
+    New(Box<ProjectArgs>),
+
+    #[command(about = "Fuzzy search dependencies by id, name, description or category")]
+    Search {
+        term: String,
+
+        #[arg(short = 'c', long, help = "Only search categories whose name contains this text")]
+        category: Option<String>,
+
+        #[arg(long, default_value_t = 20, help = "Maximum number of results to show")]
+        limit: usize,
+    },
+
+    #[command(about = "Add starters to the pom.xml or build.gradle(.kts) of an existing project")]
+    Add {
+        #[arg(required = true, value_name = "DEP_ID", help = "Dependency ids, e.g. web data-jpa")]
+        ids: Vec<String>,
+
+        #[arg(short = 'p', long, value_name = "DIR", help = "Project directory, defaults to the current directory")]
+        project_dir: Option<PathBuf>,
+
+        #[arg(short = 'b', long, help = "Spring Boot version, detected from the build file when omitted")]
+        boot_version: Option<String>,
+    },
+
+    #[command(about = "Generate a project from a start.spring.io share link")]
+    ImportUrl {
+        #[arg(help = "Share link, e.g. 'https://start.spring.io/#!type=maven-project&dependencies=web'")]
+        url: String,
+    },
+
+    #[command(about = "Print the start.spring.io share link for a saved answers file")]
+    ExportUrl {
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
+
+    #[command(about = "Show and change the xspring config file")]
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+
+    #[command(about = "Manage named presets of project options")]
+    Preset {
+        #[command(subcommand)]
+        command: PresetCommand,
     },
 
     #[command(group(
@@ -33,5 +95,25 @@ pub enum Commands{
 
         #[arg(short = 'd', long, action = ArgAction::SetTrue)]
         deps: bool,
+
+        // `requires` alone counts any flag of the required group as `--deps`, hence the conflicts
+        #[arg(long, action = ArgAction::SetTrue, requires = "deps", conflicts_with_all = ["java", "boot", "project_type", "language"], help = "Together with --deps, show descriptions and documentation links for every dependency")]
+        details: bool,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{error::ErrorKind, Parser};
+    use crate::cli::root::Cli;
+
+    #[test]
+    fn list_details_only_goes_with_deps() {
+        assert!(Cli::try_parse_from(["xspring", "list", "--deps", "--details"]).is_ok());
+
+        let err = Cli::try_parse_from(["xspring", "list", "--java", "--details"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+        let err = Cli::try_parse_from(["xspring", "list", "--details"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
     }
 }
@@ -0,0 +1,30 @@
+use clap::Subcommand;
+use crate::cli::commands::project::ProjectArgs;
+
+#[derive(Subcommand, Debug)]
+pub enum PresetCommand {
+    #[command(about = "Save the given flags, on top of the --from file, as a named preset")]
+    Save {
+        #[arg(id = "preset_name", value_name = "NAME")]
+        preset_name: String,
+
+        #[arg(long, help = "Replace the preset if it already exists")]
+        overwrite: bool,
+
+        #[command(flatten)]
+        project: Box<ProjectArgs>,
+    },
+
+    #[command(about = "List the saved presets")]
+    List,
+
+    #[command(about = "Print the values of a preset")]
+    Show {
+        name: String,
+    },
+
+    #[command(about = "Delete a preset")]
+    Delete {
+        name: String,
+    },
+}
@@ -0,0 +1,66 @@
+use clap::Args;
+use crate::models::answers::Answers;
+
+#[derive(Args, Debug, Default)]
+pub struct ProjectArgs {
+    #[arg(short = 'g', long)]
+    pub group_id: Option<String>,
+
+    #[arg(short = 'a', long)]
+    pub artifact_id: Option<String>,
+
+    #[arg(short = 'n', long)]
+    pub name: Option<String>,
+
+    #[arg(long)]
+    pub description: Option<String>,
+
+    #[arg(long, help = "Base package of the sources, derived by the Initializr from the group and artifact ids by default")]
+    pub package_name: Option<String>,
+
+    #[arg(short = 't', long = "type", help = "Project type id, e.g. maven-project or gradle-project")]
+    pub project_type: Option<String>,
+
+    #[arg(short = 'l', long)]
+    pub language: Option<String>,
+
+    #[arg(short = 'b', long)]
+    pub boot_version: Option<String>,
+
+    #[arg(short = 'p', long)]
+    pub packaging: Option<String>,
+
+    #[arg(short = 'j', long)]
+    pub java_version: Option<String>,
+
+    #[arg(short = 'd', long, value_delimiter = ',', help = "Comma separated dependency ids, e.g. web,data-jpa")]
+    pub dependencies: Vec<String>,
+
+    #[arg(long, help = "Name of the generated project folder, defaults to the artifact id")]
+    pub base_dir: Option<String>,
+}
+
+impl ProjectArgs {
+    pub fn into_answers(self) -> Answers {
+        let dependencies = if self.dependencies.is_empty() {
+            None
+        } else {
+            Some(self.dependencies.join(","))
+        };
+
+        Answers {
+            project_type: self.project_type,
+            language: self.language,
+            boot_version: self.boot_version,
+            group_id: self.group_id,
+            artifact_id: self.artifact_id,
+            name: self.name,
+            description: self.description,
+            package_name: self.package_name,
+            packaging: self.packaging,
+            java_version: self.java_version,
+            dependencies,
+            base_dir: self.base_dir,
+        }
+    }
+}
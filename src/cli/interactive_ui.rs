@@ -1,4 +1,8 @@
+use std::fmt;
 use inquire::ui::{Color, RenderConfig, Styled};
+use crate::models::spring::Value;
+
+const DESCRIPTION_WIDTH: usize = 70;
 
 // TODO: MAKE FUNCTIONS FOR STYLING INTERACTIVE ELEMENTS
 pub fn base_config(prefix: &'static str) -> RenderConfig<'static> {
@@ -6,3 +10,28 @@ pub fn base_config(prefix: &'static str) -> RenderConfig<'static> {
         .with_prompt_prefix(Styled::new(prefix))
         .with_answered_prompt_prefix(Styled::new("✓").with_fg(Color::LightGreen))
 }
+
+/// Dependency entry for the dependency prompts, showing the description next to the name
+/// so the highlighted item explains itself. Typing also filters over the description.
+#[derive(Debug, Clone, Copy)]
+pub struct DependencyOption<'a>(pub &'a Value);
+
+impl fmt::Display for DependencyOption<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.description.as_deref() {
+            Some(description) if !description.is_empty() => {
+                write!(f, "{} - {}", self.0.name, truncate(description, DESCRIPTION_WIDTH))
+            }
+            _ => write!(f, "{}", self.0.name),
+        }
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+
+    let truncated = text.chars().take(width.saturating_sub(1)).collect::<String>();
+    format!("{}…", truncated.trim_end())
+}
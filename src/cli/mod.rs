@@ -1,3 +1,4 @@
 pub mod root;
 pub mod commands;
 pub mod interactive_ui;
+pub mod tui_ui;
@@ -1,5 +1,7 @@
 use crate::cli::commands::Commands;
-use clap::Parser;
+use crate::client::archive::{OutputMode, OverwritePolicy};
+use anyhow::{anyhow, Result};
+use clap::{Parser, ValueEnum};
 use std::path::PathBuf;
 use clap_verbosity_flag::Verbosity;
 
@@ -15,6 +17,87 @@ pub struct Cli{
     #[arg(short = 'o', long, global = true)]
     pub output: Option<PathBuf>,
 
+    #[arg(long, global = true, env = "XSPRING_SERVER", help = "Base URL of the Spring Initializr instance to use [default: https://start.spring.io]")]
+    pub server: Option<String>,
+
+    #[arg(long, global = true, env = "XSPRING_OFFLINE", help = "Serve metadata from the local cache without touching the network")]
+    pub offline: bool,
+
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table, help = "Output format for listings, search results and dry runs")]
+    pub format: OutputFormat,
+
+    #[arg(long, global = true, value_name = "FILE", help = "Replay answers from a .toml, .json or .yaml file and skip the prompts they answer")]
+    pub from: Option<PathBuf>,
+
+    #[arg(long, global = true, value_name = "NAME", help = "Use the values of a saved preset and skip the prompts they answer")]
+    pub preset: Option<String>,
+
+    #[arg(long, global = true, requires = "preset", help = "Prefill the prompts with the preset instead of skipping them")]
+    pub preset_as_defaults: bool,
+
+    #[arg(long, global = true, value_name = "FILE", help = "Save the chosen answers to a .toml, .json or .yaml file")]
+    pub save_answers: Option<PathBuf>,
+
+    #[arg(long, global = true, conflicts_with_all = ["merge", "fail_if_exists"], help = "Overwrite files of an existing project directory")]
+    pub force: bool,
+
+    #[arg(long, global = true, conflicts_with = "fail_if_exists", help = "Keep files of an existing project directory and only add the missing ones")]
+    pub merge: bool,
+
+    #[arg(long, global = true, help = "Fail if the project directory already exists")]
+    pub fail_if_exists: bool,
+
+    #[arg(long, global = true, value_enum, default_value_t = OutputMode::Zip, help = "Fetch the project as a zip or tgz archive, or only its build file into the output directory")]
+    pub output_mode: OutputMode,
+
+    #[arg(long, global = true, help = "Download the project and show its files without writing anything")]
+    pub dry_run: bool,
+
+    #[arg(long, global = true, help = "Initialize a git repository with an initial commit in the generated project")]
+    pub git: bool,
+
+    #[arg(long, global = true, conflicts_with = "git", help = "Don't initialize a git repository, even if the config file enables it")]
+    pub no_git: bool,
+
+    #[arg(long, global = true, value_name = "URL", help = "Add URL as the origin remote of the initialized repository")]
+    pub git_remote: Option<String>,
+
+    #[arg(long, global = true, value_name = "MESSAGE", help = "Message of the initial commit [default: Initial commit from xspring]")]
+    pub commit_message: Option<String>,
+
+    #[arg(long, global = true, help = "Don't run the postGenerate hooks of the config file")]
+    pub no_hooks: bool,
+
     #[command(flatten)]
     pub verbose: Verbosity,
 }
+
+impl Cli {
+    // clap does not catch conflicts between global flags given before and after the subcommand
+    pub fn overwrite_policy(&self) -> Result<OverwritePolicy> {
+        if [self.force, self.merge, self.fail_if_exists].iter().filter(|flag| **flag).count() > 1 {
+            return Err(anyhow!("Only one of '--force', '--merge' and '--fail-if-exists' can be used"));
+        }
+
+        let policy = if self.force {
+            OverwritePolicy::Force
+        } else if self.merge {
+            OverwritePolicy::Merge
+        } else if self.fail_if_exists {
+            OverwritePolicy::FailIfExists
+        } else {
+            OverwritePolicy::Abort
+        };
+
+        Ok(policy)
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    Json,
+    Yaml,
+    Csv,
+    #[default]
+    Table,
+}
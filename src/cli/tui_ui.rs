@@ -0,0 +1,165 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+use crate::handlers::tui::{App, DependencyRow, Focus, FORM_FIELDS};
+
+const LABEL_WIDTH: usize = 21;
+const HELP: &str = "Tab/Shift+Tab switch panels • ↑↓ move • ←→ change a choice • Space select a dependency • Ctrl+G generate • Esc quit";
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let warnings = app.warnings();
+    let status_lines = warnings.len() + usize::from(app.message.is_some());
+    let [main, problems, help] = Layout::vertical([
+        Constraint::Min(10),
+        Constraint::Length(status_lines.clamp(1, 5) as u16 + 2),
+        Constraint::Length(1),
+    ])
+        .areas(frame.area());
+    let [form, dependencies] = Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)]).areas(main);
+
+    draw_form(frame, app, form);
+    draw_dependencies(frame, app, dependencies);
+    draw_problems(frame, app, &warnings, problems);
+    frame.render_widget(Paragraph::new(HELP).style(Style::new().fg(Color::DarkGray)), help);
+}
+
+fn draw_form(frame: &mut Frame, app: &App, area: Rect) {
+    let mut lines = FORM_FIELDS.iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let focused = app.focus == Focus::Form(index);
+            let value = match (app.choices(*field).is_some(), focused) {
+                (true, true) => format!("◀ {} ▶", app.display_value(*field)),
+                (false, true) => format!("{}▏", app.display_value(*field)),
+                (_, false) => app.display_value(*field),
+            };
+            let style = if focused { Style::new().add_modifier(Modifier::REVERSED) } else { Style::new() };
+
+            Line::from(vec![
+                Span::styled(format!("{:LABEL_WIDTH$}", field.label()), Style::new().fg(Color::Cyan)),
+                Span::styled(value, style),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    let generate_style = if app.focus == Focus::Generate {
+        Style::new().fg(Color::Black).bg(Color::LightGreen).add_modifier(Modifier::BOLD)
+    } else {
+        Style::new().fg(Color::LightGreen)
+    };
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled("[ Generate ]", generate_style)));
+
+    let block = panel("Project", matches!(app.focus, Focus::Form(_) | Focus::Generate));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_dependencies(frame: &mut Frame, app: &App, area: Rect) {
+    let focused = app.focus == Focus::Dependencies;
+    let [search, list, details, selected] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(5),
+        Constraint::Length(6),
+        Constraint::Length(4),
+    ])
+        .areas(area);
+
+    let cursor = if focused { "▏" } else { "" };
+    let search_text = if app.search.is_empty() && !focused {
+        Span::styled("Type to search every category", Style::new().fg(Color::DarkGray))
+    } else {
+        Span::raw(format!("{}{}", app.search, cursor))
+    };
+    frame.render_widget(Paragraph::new(search_text).block(panel("Search dependencies", focused)), search);
+
+    let rows = app.dependency_rows();
+    let mut items = Vec::new();
+    let mut selected_item = None;
+    let mut previous_category = None;
+    for (index, row) in rows.iter().enumerate() {
+        // Category headers only make sense in the unfiltered list
+        if app.search.trim().is_empty() && previous_category != Some(row.category) {
+            items.push(ListItem::new(Span::styled(row.category, Style::new().add_modifier(Modifier::BOLD))));
+            previous_category = Some(row.category);
+        }
+        if index == app.dependency_cursor {
+            selected_item = Some(items.len());
+        }
+        items.push(dependency_item(row, !app.search.trim().is_empty()));
+    }
+
+    let mut state = ListState::default().with_selected(selected_item.filter(|_| focused));
+    let highlight = Style::new().add_modifier(Modifier::REVERSED);
+    let title = format!("Dependencies ({})", rows.len());
+    frame.render_stateful_widget(List::new(items).block(panel(&title, focused)).highlight_style(highlight), list, &mut state);
+
+    let details_text = match rows.get(app.dependency_cursor) {
+        Some(row) => {
+            let mut lines = vec![Line::from(vec![
+                Span::styled(row.dep.name.as_str(), Style::new().add_modifier(Modifier::BOLD)),
+                Span::styled(format!("  {} · {}", row.dep.id, row.category), Style::new().fg(Color::DarkGray)),
+            ])];
+            lines.push(Line::raw(row.dep.description.clone().unwrap_or_default()));
+            if !row.compatible {
+                let range = row.dep.version_range().map(|range| range.to_string()).unwrap_or_default();
+                lines.push(Line::styled(format!("⚠ Requires Spring Boot {}", range), Style::new().fg(Color::Yellow)));
+            }
+            lines
+        }
+        None => vec![Line::styled("No dependency matches the search", Style::new().fg(Color::DarkGray))],
+    };
+    frame.render_widget(Paragraph::new(details_text).wrap(Wrap { trim: true }).block(panel("Details", false)), details);
+
+    let ids = app.selected_ids();
+    let names = app.metadata.dependencies.values.iter()
+        .flat_map(|category| category.values.iter())
+        .filter(|dep| ids.contains(&dep.id.as_str()))
+        .map(|dep| dep.name.as_str())
+        .collect::<Vec<_>>();
+    let selected_text = if names.is_empty() { "None yet".to_string() } else { names.join(", ") };
+    frame.render_widget(
+        Paragraph::new(selected_text).wrap(Wrap { trim: true }).block(panel(&format!("Selected ({})", names.len()), false)),
+        selected,
+    );
+}
+
+fn dependency_item<'a>(row: &DependencyRow<'a>, with_category: bool) -> ListItem<'a> {
+    let check = if row.selected { "[x]" } else { "[ ]" };
+    let mut spans = vec![Span::raw(format!("  {} {}", check, row.dep.name))];
+    if with_category {
+        spans.push(Span::styled(format!("  {}", row.category), Style::new().fg(Color::DarkGray)));
+    }
+
+    let style = match (row.compatible, row.selected) {
+        (false, true) => Style::new().fg(Color::Red),
+        (false, false) => Style::new().fg(Color::DarkGray),
+        (true, true) => Style::new().fg(Color::LightGreen),
+        (true, false) => Style::new(),
+    };
+    if !row.compatible {
+        spans.push(Span::raw("  ⚠ incompatible"));
+    }
+
+    ListItem::new(Line::from(spans)).style(style)
+}
+
+fn draw_problems(frame: &mut Frame, app: &App, warnings: &[String], area: Rect) {
+    let mut lines = app.message.iter()
+        .map(|message| Line::styled(message.as_str(), Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD)))
+        .collect::<Vec<_>>();
+    lines.extend(warnings.iter().map(|warning| Line::styled(format!("⚠ {}", warning), Style::new().fg(Color::Red))));
+    if lines.is_empty() {
+        lines.push(Line::styled("✓ Ready to generate", Style::new().fg(Color::LightGreen)));
+    }
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }).block(panel("Status", false)), area);
+}
+
+fn panel(title: &str, focused: bool) -> Block<'static> {
+    let border = if focused { Style::new().fg(Color::LightGreen) } else { Style::new().fg(Color::DarkGray) };
+    Block::bordered().title(format!(" {} ", title)).border_style(border)
+}
@@ -0,0 +1,427 @@
+use std::{fs, io::{Cursor, Read}, path::{Component, Path, PathBuf}};
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use flate2::read::GzDecoder;
+use tracing::debug;
+use zip::ZipArchive;
+
+const WRAPPER_SCRIPTS: [&str; 2] = ["mvnw", "gradlew"];
+
+/// Which Initializr endpoint the project is fetched from.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputMode {
+    /// The whole project from `/starter.zip`
+    #[default]
+    Zip,
+    /// The whole project from `/starter.tgz`
+    Tgz,
+    /// Only the `pom.xml`, `build.gradle` or `build.gradle.kts` matching the project type
+    BuildFile,
+}
+
+/// What to do when the generated project would land on files that already exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverwritePolicy {
+    /// Write into an existing directory, but refuse to replace any file.
+    #[default]
+    Abort,
+    /// Refuse to write as soon as the project directory exists.
+    FailIfExists,
+    /// Keep existing files and only add the missing ones.
+    Merge,
+    /// Replace existing files.
+    Force,
+}
+
+#[derive(Debug)]
+pub struct ArchiveEntry {
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub unix_mode: Option<u32>,
+    pub contents: Vec<u8>,
+}
+
+/// A generated project held in memory, with every entry path already checked
+/// to stay inside the directory it gets extracted to.
+#[derive(Debug, Default)]
+pub struct ProjectArchive {
+    pub entries: Vec<ArchiveEntry>,
+}
+
+#[derive(Debug, Default)]
+pub struct ExtractReport {
+    pub written: Vec<PathBuf>,
+    pub overwritten: Vec<PathBuf>,
+    pub skipped: Vec<PathBuf>,
+    /// Wrapper scripts whose mode lacked the execute bit and had it added.
+    pub executables: Vec<PathBuf>,
+}
+
+impl ProjectArchive {
+    pub fn from_zip(bytes: &[u8]) -> Result<Self> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes))
+            .with_context(|| "The downloaded project is not a valid zip archive")?;
+
+        let mut entries = Vec::with_capacity(archive.len());
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index)
+                .with_context(|| format!("Failed to read entry {} of the project archive", index))?;
+            if file.is_symlink() {
+                return Err(anyhow!("Refusing to extract '{}', the project archive must not contain symlinks", file.name()));
+            }
+            let path = file.enclosed_name()
+                .ok_or_else(|| anyhow!("Refusing to extract '{}', it would be written outside of the output directory", file.name()))?;
+
+            let mut contents = Vec::new();
+            if !file.is_dir() {
+                file.read_to_end(&mut contents)
+                    .with_context(|| format!("Failed to read '{}' from the project archive", file.name()))?;
+            }
+
+            entries.push(ArchiveEntry {
+                path,
+                is_dir: file.is_dir(),
+                unix_mode: file.unix_mode(),
+                contents,
+            });
+        }
+
+        Ok(ProjectArchive { entries })
+    }
+
+    pub fn from_tgz(bytes: &[u8]) -> Result<Self> {
+        let mut archive = tar::Archive::new(GzDecoder::new(bytes));
+
+        let mut entries = Vec::new();
+        for file in archive.entries().with_context(|| "The downloaded project is not a valid tgz archive")? {
+            let mut file = file.with_context(|| "Failed to read an entry of the project archive")?;
+            let name = file.path()
+                .with_context(|| "Failed to read an entry path of the project archive")?
+                .into_owned();
+            let entry_type = file.header().entry_type();
+            if entry_type.is_symlink() || entry_type.is_hard_link() {
+                return Err(anyhow!("Refusing to extract '{}', the project archive must not contain links", name.display()));
+            }
+            let is_root = name.components().all(|component| component == Component::CurDir);
+            if is_root || (!entry_type.is_file() && !entry_type.is_dir()) {
+                debug!("Skipping the {:?} entry '{}' of the project archive", entry_type, name.display());
+                continue;
+            }
+            let path = enclosed_path(&name)
+                .ok_or_else(|| anyhow!("Refusing to extract '{}', it would be written outside of the output directory", name.display()))?;
+
+            let mut contents = Vec::new();
+            if entry_type.is_file() {
+                file.read_to_end(&mut contents)
+                    .with_context(|| format!("Failed to read '{}' from the project archive", name.display()))?;
+            }
+
+            entries.push(ArchiveEntry {
+                path,
+                is_dir: entry_type.is_dir(),
+                unix_mode: file.header().mode().ok(),
+                contents,
+            });
+        }
+
+        Ok(ProjectArchive { entries })
+    }
+
+    /// Wraps a single downloaded file, such as a build file, so it is written like an archive.
+    pub fn from_file(name: &str, contents: Vec<u8>) -> Self {
+        ProjectArchive {
+            entries: vec![ArchiveEntry {
+                path: PathBuf::from(name),
+                is_dir: false,
+                unix_mode: None,
+                contents,
+            }],
+        }
+    }
+
+    /// Top level files and directories of the archive, usually just the project directory.
+    pub fn roots(&self) -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = Vec::new();
+        for entry in &self.entries {
+            if let Some(Component::Normal(root)) = entry.path.components().next() {
+                let root = PathBuf::from(root);
+                if !roots.contains(&root) {
+                    roots.push(root);
+                }
+            }
+        }
+
+        roots
+    }
+
+    /// Files of the archive that already exist below `out_dir`, dangling symlinks included.
+    pub fn existing_files(&self, out_dir: &Path) -> Vec<PathBuf> {
+        self.entries.iter()
+            .filter(|entry| !entry.is_dir && path_exists(&out_dir.join(&entry.path)))
+            .map(|entry| entry.path.clone())
+            .collect()
+    }
+
+    pub fn extract(&self, out_dir: &Path, policy: OverwritePolicy) -> Result<ExtractReport> {
+        if policy == OverwritePolicy::FailIfExists {
+            let existing = self.roots().into_iter()
+                .filter(|root| path_exists(&out_dir.join(root)))
+                .collect::<Vec<_>>();
+            if !existing.is_empty() {
+                return Err(anyhow!(
+                    "{} already exists in {:?}, not writing the project",
+                    display_paths(&existing),
+                    out_dir
+                ));
+            }
+        }
+
+        let existing = self.existing_files(out_dir);
+        if policy == OverwritePolicy::Abort && !existing.is_empty() {
+            return Err(anyhow!(
+                "The following files already exist in {:?} and would be overwritten:\n  {}\nUse '--force' to overwrite them or '--merge' to keep them and only add the missing files",
+                out_dir,
+                existing.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join("\n  ")
+            ));
+        }
+
+        fs::create_dir_all(out_dir)
+            .with_context(|| format!("Failed to create the output directory {:?}", out_dir))?;
+        let root = out_dir.canonicalize()
+            .with_context(|| format!("Failed to resolve the output directory {:?}", out_dir))?;
+
+        let mut report = ExtractReport::default();
+        for entry in &self.entries {
+            let target = out_dir.join(&entry.path);
+            if entry.is_dir {
+                ensure_inside(&root, &target)?;
+                fs::create_dir_all(&target)
+                    .with_context(|| format!("Failed to create the directory {:?}", target))?;
+                continue;
+            }
+
+            if let Some(parent) = target.parent() {
+                ensure_inside(&root, parent)?;
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create the directory {:?}", parent))?;
+            }
+
+            let exists = existing.contains(&entry.path);
+            if exists && policy == OverwritePolicy::Merge {
+                debug!("Keeping the existing file {:?}", target);
+                report.skipped.push(entry.path.clone());
+                continue;
+            }
+
+            // Replace symlinks instead of writing through them, dangling ones included
+            if target.symlink_metadata().is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+                fs::remove_file(&target)
+                    .with_context(|| format!("Failed to replace the symlink {:?}", target))?;
+            }
+            fs::write(&target, &entry.contents)
+                .with_context(|| format!("Failed to write {:?}", target))?;
+            set_unix_mode(&target, entry.unix_mode);
+            // Some archives carry no permissions at all, ./mvnw has to work right after generation
+            if is_wrapper_script(&entry.path) && make_executable(&target) {
+                report.executables.push(entry.path.clone());
+            }
+
+            if exists {
+                report.overwritten.push(entry.path.clone());
+            } else {
+                report.written.push(entry.path.clone());
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+// Unlike Path::exists, does not follow symlinks, so a dangling one still counts
+fn path_exists(path: &Path) -> bool {
+    path.symlink_metadata().is_ok()
+}
+
+// Same rules as zip's enclosed_name: relative, without '..' and without a drive or root
+fn enclosed_path(name: &Path) -> Option<PathBuf> {
+    let mut path = PathBuf::new();
+    for component in name.components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+
+    if path.as_os_str().is_empty() { None } else { Some(path) }
+}
+
+// Guards against directories of an existing project being symlinks that point elsewhere.
+// Resolves the closest existing ancestor, so nothing gets created before the check.
+fn ensure_inside(root: &Path, dir: &Path) -> Result<()> {
+    let existing = dir.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or(dir);
+    let resolved = existing.canonicalize()
+        .with_context(|| format!("Failed to resolve the directory {:?}", existing))?;
+    if !resolved.starts_with(root) {
+        return Err(anyhow!("Refusing to write to {:?}, it resolves to {:?} outside of the output directory", dir, resolved));
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn set_unix_mode(path: &Path, unix_mode: Option<u32>) {
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Some(mode) = unix_mode.map(|mode| mode & 0o777).filter(|mode| *mode != 0)
+        && let Err(err) = fs::set_permissions(path, fs::Permissions::from_mode(mode))
+    {
+        tracing::warn!("Failed to set the permissions of {:?}: {:?}", path, err);
+    }
+}
+
+#[cfg(not(unix))]
+fn set_unix_mode(_path: &Path, _unix_mode: Option<u32>) {}
+
+pub fn is_wrapper_script(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| WRAPPER_SCRIPTS.contains(&name))
+}
+
+/// Adds the execute bit wherever the read bit is set, returns whether the mode had to change.
+#[cfg(unix)]
+fn make_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = match fs::metadata(path) {
+        Ok(metadata) => metadata.permissions().mode(),
+        Err(err) => {
+            tracing::warn!("Failed to read the permissions of {:?}: {:?}", path, err);
+            return false;
+        }
+    };
+    let executable = mode | ((mode & 0o444) >> 2);
+    if executable == mode {
+        return false;
+    }
+    if let Err(err) = fs::set_permissions(path, fs::Permissions::from_mode(executable)) {
+        tracing::warn!("Failed to mark {:?} as executable: {:?}", path, err);
+        return false;
+    }
+
+    true
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> bool {
+    false
+}
+
+fn display_paths(paths: &[PathBuf]) -> String {
+    paths.iter()
+        .map(|path| format!("'{}'", path.display()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+    use super::*;
+
+    /// An output directory whose `demo/<name>` is a dangling symlink to a file outside of it.
+    fn dangling_link(name: &str) -> (TempDir, PathBuf, PathBuf) {
+        let temp = tempfile::tempdir().unwrap();
+        let out_dir = temp.path().join("out");
+        let outside = temp.path().join("outside");
+        fs::create_dir_all(out_dir.join("demo")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+
+        let target = outside.join("pwned");
+        symlink(&target, out_dir.join("demo").join(name)).unwrap();
+        (temp, out_dir, target)
+    }
+
+    #[test]
+    fn dangling_symlink_counts_as_existing() {
+        let (_temp, out_dir, outside) = dangling_link("pom.xml");
+        let archive = ProjectArchive::from_file("demo/pom.xml", b"<project/>".to_vec());
+
+        assert_eq!(archive.existing_files(&out_dir), vec![PathBuf::from("demo/pom.xml")]);
+        assert!(archive.extract(&out_dir, OverwritePolicy::Abort).is_err());
+        assert!(!outside.exists());
+    }
+
+    #[test]
+    fn force_replaces_dangling_symlink_instead_of_writing_through_it() {
+        let (_temp, out_dir, outside) = dangling_link("pom.xml");
+        let archive = ProjectArchive::from_file("demo/pom.xml", b"<project/>".to_vec());
+
+        let report = archive.extract(&out_dir, OverwritePolicy::Force).unwrap();
+
+        assert_eq!(report.overwritten, vec![PathBuf::from("demo/pom.xml")]);
+        assert!(!outside.exists());
+        let written = out_dir.join("demo/pom.xml");
+        assert!(!written.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(written).unwrap(), "<project/>");
+    }
+
+    #[test]
+    fn merge_keeps_symlinks_without_following_them() {
+        let (_temp, out_dir, outside) = dangling_link("pom.xml");
+        let archive = ProjectArchive::from_file("demo/pom.xml", b"<project/>".to_vec());
+
+        let report = archive.extract(&out_dir, OverwritePolicy::Merge).unwrap();
+
+        assert_eq!(report.skipped, vec![PathBuf::from("demo/pom.xml")]);
+        assert!(!outside.exists());
+    }
+
+    #[test]
+    fn merge_keeps_an_existing_gitignore_as_it_is() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(temp.path().join("demo")).unwrap();
+        fs::write(temp.path().join("demo/.gitignore"), "custom").unwrap();
+        let archive = ProjectArchive::from_file("demo/.gitignore", b"target/\n".to_vec());
+
+        let report = archive.extract(temp.path(), OverwritePolicy::Merge).unwrap();
+
+        assert_eq!(report.skipped, vec![PathBuf::from("demo/.gitignore")]);
+        assert_eq!(fs::read_to_string(temp.path().join("demo/.gitignore")).unwrap(), "custom");
+    }
+
+    fn wrapper_archive(unix_mode: u32) -> ProjectArchive {
+        ProjectArchive {
+            entries: vec![ArchiveEntry { path: PathBuf::from("demo/mvnw"), is_dir: false, unix_mode: Some(unix_mode), contents: b"#!/bin/sh\n".to_vec() }],
+        }
+    }
+
+    fn mode(path: &Path) -> u32 {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(path).unwrap().permissions().mode() & 0o777
+    }
+
+    #[test]
+    fn marks_a_non_executable_wrapper_script_executable() {
+        let temp = tempfile::tempdir().unwrap();
+
+        let report = wrapper_archive(0o644).extract(temp.path(), OverwritePolicy::Abort).unwrap();
+
+        assert_eq!(report.executables, vec![PathBuf::from("demo/mvnw")]);
+        assert_eq!(mode(&temp.path().join("demo/mvnw")), 0o755);
+    }
+
+    #[test]
+    fn leaves_an_executable_wrapper_script_out_of_the_report() {
+        let temp = tempfile::tempdir().unwrap();
+
+        let report = wrapper_archive(0o755).extract(temp.path(), OverwritePolicy::Abort).unwrap();
+
+        assert!(report.executables.is_empty());
+        assert_eq!(mode(&temp.path().join("demo/mvnw")), 0o755);
+    }
+}
@@ -0,0 +1,132 @@
+use std::{env, fs, path::{Path, PathBuf}, time::{Duration, SystemTime, UNIX_EPOCH}};
+use anyhow::{anyhow, Context, Result};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+// Everything but letters, digits, '-' and '.' is encoded, so every server gets its own file name
+const FILE_NAME: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.');
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CachedMetadata {
+    pub server: String,
+    pub etag: Option<String>,
+    pub fetched_at: u64,
+    pub body: String,
+}
+
+impl CachedMetadata {
+    pub fn new(server: &str, etag: Option<String>, body: String) -> Self {
+        Self {
+            server: server.to_string(),
+            etag,
+            fetched_at: now(),
+            body,
+        }
+    }
+
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        now().saturating_sub(self.fetched_at) < ttl.as_secs()
+    }
+
+    pub fn touch(&mut self) {
+        self.fetched_at = now();
+    }
+}
+
+pub fn cache_dir() -> Result<PathBuf> {
+    if let Some(path) = env::var_os("XSPRING_CACHE_DIR") {
+        return Ok(PathBuf::from(path));
+    }
+
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| anyhow!("Could not determine the cache directory for this platform"))?;
+
+    Ok(cache_dir.join("xspring"))
+}
+
+pub fn cache_path(server: &str) -> Result<PathBuf> {
+    Ok(cache_dir()?.join(cache_file_name(server)))
+}
+
+pub fn load(server: &str) -> Result<Option<CachedMetadata>> {
+    read_cache(&cache_path(server)?, server)
+}
+
+fn cache_file_name(server: &str) -> String {
+    format!("metadata-{}.json", utf8_percent_encode(server, FILE_NAME))
+}
+
+/// A cache written for another server, e.g. on a case-insensitive file system, counts as a miss.
+fn read_cache(path: &Path, server: &str) -> Result<Option<CachedMetadata>> {
+    if !path.exists() {
+        debug!("No cached metadata at {:?}", path);
+        return Ok(None);
+    }
+
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read the metadata cache at {:?}", path))?;
+    let cached = serde_json::from_str::<CachedMetadata>(&contents)
+        .with_context(|| format!("Failed to parse the metadata cache at {:?}", path))?;
+    if cached.server != server {
+        debug!("The metadata cache at {:?} belongs to {}, not {}", path, cached.server, server);
+        return Ok(None);
+    }
+
+    Ok(Some(cached))
+}
+
+pub fn store(cached: &CachedMetadata) -> Result<()> {
+    let path = cache_path(&cached.server)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create the cache directory {:?}", parent))?;
+    }
+
+    let contents = serde_json::to_string(cached)?;
+    fs::write(&path, contents)
+        .with_context(|| format!("Failed to write the metadata cache at {:?}", path))?;
+    debug!("Stored metadata for {} in {:?}", cached.server, path);
+
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gives_every_server_its_own_file_name() {
+        assert_eq!(cache_file_name("https://start.spring.io"), "metadata-https%3A%2F%2Fstart.spring.io.json");
+
+        let servers = ["http://a.b", "http://a_b", "http://a-b", "http://a/b", "http://a%2Fb", "http://a:8080", "http://a_8080"];
+        let mut names = servers.map(cache_file_name).to_vec();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), servers.len());
+    }
+
+    #[test]
+    fn treats_a_cache_of_another_server_as_a_miss() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("metadata.json");
+        assert!(read_cache(&path, "http://a_b").unwrap().is_none());
+
+        let cached = CachedMetadata::new("http://a.b", Some("\"v1\"".to_string()), "{}".to_string());
+        fs::write(&path, serde_json::to_string(&cached).unwrap()).unwrap();
+
+        assert!(read_cache(&path, "http://a_b").unwrap().is_none());
+        let loaded = read_cache(&path, "http://a.b").unwrap().unwrap();
+        assert_eq!(loaded.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(loaded.body, "{}");
+    }
+}
@@ -1 +1,3 @@
+pub mod archive;
+pub mod cache;
 pub mod spring_initializr;
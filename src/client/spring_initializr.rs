@@ -1,52 +1,237 @@
-use std::{io::Cursor, path::PathBuf};
+use std::{path::PathBuf, time::Duration};
 use anyhow::{anyhow, Context, Result};
-use reqwest::Client;
-use tracing::info;
-use zip::ZipArchive;
-use crate::models::spring::{InitializrMetadata, QueryParam};
+use reqwest::{header::ETAG, Client, StatusCode};
+use tracing::{debug, info, warn};
+use crate::{
+    client::{archive::{OutputMode, OverwritePolicy, ProjectArchive}, cache::{self, CachedMetadata, DEFAULT_CACHE_TTL}},
+    models::spring::{DependencyDetails, InitializrMetadata, QueryParam},
+};
 
-pub async fn get_metadata() -> Result<InitializrMetadata>{
-    let client = Client::new();
+pub const DEFAULT_SERVER: &str = "https://start.spring.io";
 
-    let response = client
-        .get("https://start.spring.io")
-        .header("Accept", "application/json")
-        .header("User-Agent", "XSpring CLI tool")
-        .send()
-        .await.with_context(|| "Failed to fetch metadata from spring api")?;
+#[derive(Debug, Clone)]
+pub struct SpringInitializr {
+    client: Client,
+    server: String,
+    offline: bool,
+    cache_ttl: Duration,
+}
+
+impl SpringInitializr {
+    pub fn new(server: impl Into<String>) -> Self {
+        let server: String = server.into();
 
-    if !response.status().is_success() {
-        return Err(anyhow!("Failed to fetch metadata {}", response.status()))
+        Self {
+            client: Client::new(),
+            server: server.trim_end_matches('/').to_string(),
+            offline: false,
+            cache_ttl: DEFAULT_CACHE_TTL,
+        }
     }
 
-    let metadata = response.json::<InitializrMetadata>().await.with_context(|| "Failed to deserialize the metadata fetched from the spring api")?;
+    pub fn with_server(mut self, server: impl Into<String>) -> Self {
+        let server: String = server.into();
+        self.server = server.trim_end_matches('/').to_string();
+        self
+    }
 
-    Ok(metadata)
-}
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    pub fn server(&self) -> &str {
+        &self.server
+    }
 
-pub async fn generate_project(params: QueryParam, out_dir: PathBuf) -> Result<()> {
-    let client = Client::new();
+    pub async fn get_metadata(&self) -> Result<InitializrMetadata>{
+        let cached = cache::load(&self.server).unwrap_or_else(|err| {
+            warn!("Ignoring unreadable metadata cache: {:?}", err);
+            None
+        });
 
-    let response = client
-        .get("https://start.spring.io/starter.zip")
-        .query(&params)
-        .send()
-        .await?;
+        if self.offline {
+            let cached = cached.ok_or_else(|| anyhow!(
+                "No cached metadata for {} is available in offline mode. Run xspring once without '--offline' to populate the cache at {:?}",
+                self.server,
+                cache::cache_path(&self.server).unwrap_or_default()
+            ))?;
+            debug!("Serving metadata for {} from the cache (offline)", self.server);
+            return parse_metadata(&self.server, &cached.body);
+        }
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let body = response.text().await.unwrap_or_else(|_| "Could not read error body".to_string());
-        return Err(anyhow!("Failed to generate project with status: {}. Body: {}", status, body));
+        if let Some(cached) = cached.as_ref().filter(|cached| cached.is_fresh(self.cache_ttl)) {
+            debug!("Serving fresh cached metadata for {}", self.server);
+            return parse_metadata(&self.server, &cached.body);
+        }
+
+        let mut request = self.client
+            .get(&self.server)
+            .header("Accept", "application/json")
+            .header("User-Agent", "XSpring CLI tool");
+        if let Some(etag) = cached.as_ref().and_then(|cached| cached.etag.as_ref()) {
+            request = request.header("If-None-Match", etag);
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(err) => {
+                let Some(cached) = cached else {
+                    return Err(err).with_context(|| format!("Failed to fetch metadata from {}", self.server));
+                };
+                warn!("Failed to fetch metadata from {}, falling back to the stale cache: {:?}", self.server, err);
+                eprintln!("Could not reach {}, using cached metadata instead", self.server);
+                return parse_metadata(&self.server, &cached.body);
+            }
+        };
+
+        if response.status() == StatusCode::NOT_MODIFIED && let Some(mut cached) = cached {
+            debug!("Cached metadata for {} is still valid", self.server);
+            cached.touch();
+            if let Err(err) = cache::store(&cached) {
+                warn!("Failed to refresh the metadata cache: {:?}", err);
+            }
+            return parse_metadata(&self.server, &cached.body);
+        }
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to fetch metadata from {}: {}", self.server, response.status()))
+        }
+
+        let etag = response.headers()
+            .get(ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(|etag| etag.to_string());
+        let body = response.text().await
+            .with_context(|| format!("Failed to read the metadata fetched from {}", self.server))?;
+        let metadata = parse_metadata(&self.server, &body)?;
+
+        if let Err(err) = cache::store(&CachedMetadata::new(&self.server, etag, body)) {
+            warn!("Failed to cache the metadata: {:?}", err);
+        }
+
+        Ok(metadata)
     }
 
-    let project_bytes = response.bytes().await?;
-    let cursor = Cursor::new(project_bytes);
-    let mut archive = ZipArchive::new(cursor)?;
-    archive.extract(&out_dir)?;
+    pub async fn get_dependency_details(&self, boot_version: &str) -> Result<DependencyDetails> {
+        if self.offline {
+            return Err(anyhow!("Cannot resolve dependency coordinates in offline mode, {} has to be reachable", self.server));
+        }
+
+        let url = format!("{}/dependencies", self.server);
+        let response = self.client
+            .get(&url)
+            .query(&[("bootVersion", boot_version)])
+            .header("Accept", "application/json")
+            .header("User-Agent", "XSpring CLI tool")
+            .send()
+            .await.with_context(|| format!("Failed to fetch dependency coordinates from {}", self.server))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_else(|_| "Could not read error body".to_string());
+            return Err(anyhow!("{} failed to resolve dependencies for Spring Boot {} with status: {}. Body: {}", self.server, boot_version, status, body));
+        }
 
-    let project_dir = out_dir.join(params.base_dir);
-    info!("Successfully Generated a Spring Boot project folder at {:?}", project_dir);
-    println!("Successfully Generated a Spring Boot project folder at {:?}", project_dir);
+        let details = response.json::<DependencyDetails>().await
+            .with_context(|| format!("Failed to deserialize the dependency coordinates fetched from {}", self.server))?;
+
+        Ok(details)
+    }
+
+    pub async fn download_project(&self, params: &QueryParam, mode: OutputMode) -> Result<ProjectArchive> {
+        if self.offline {
+            return Err(anyhow!("Cannot generate a project in offline mode, {} has to be reachable", self.server));
+        }
+
+        let file_name = match mode {
+            OutputMode::Zip => "starter.zip",
+            OutputMode::Tgz => "starter.tgz",
+            OutputMode::BuildFile => build_file_name(&params.project_type),
+        };
+        let url = format!("{}/{}", self.server, file_name);
+        let response = self.client
+            .get(&url)
+            .query(params)
+            .header("User-Agent", "XSpring CLI tool")
+            .send()
+            .await.with_context(|| format!("Failed to request the project from {}", self.server))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_else(|_| "Could not read error body".to_string());
+            return Err(anyhow!("{} failed to generate project with status: {}. Body: {}", self.server, status, body));
+        }
+
+        let project_bytes = response.bytes().await
+            .with_context(|| format!("Failed to download the project archive from {}", self.server))?;
+
+        match mode {
+            OutputMode::Zip => ProjectArchive::from_zip(&project_bytes),
+            OutputMode::Tgz => ProjectArchive::from_tgz(&project_bytes),
+            OutputMode::BuildFile => Ok(ProjectArchive::from_file(file_name, project_bytes.to_vec())),
+        }
+    }
+
+    pub async fn generate_project(&self, params: &QueryParam, out_dir: PathBuf, policy: OverwritePolicy, mode: OutputMode) -> Result<PathBuf> {
+        let archive = self.download_project(params, mode).await?;
+        let report = archive.extract(&out_dir, policy)?;
+        debug!("Extracted {} new files into {:?}", report.written.len(), out_dir);
+
+        if mode == OutputMode::BuildFile {
+            let build_file = out_dir.join(build_file_name(&params.project_type));
+            info!("Successfully Generated a build file at {:?}", build_file);
+            if report.skipped.is_empty() {
+                println!("Successfully Generated a build file at {:?}", build_file);
+            } else {
+                println!("Kept the existing build file at {:?}", build_file);
+            }
+            return Ok(out_dir);
+        }
+
+        let project_dir = out_dir.join(&params.base_dir);
+        info!("Successfully Generated a Spring Boot project folder at {:?}", project_dir);
+        println!("Successfully Generated a Spring Boot project folder at {:?}", project_dir);
+        if !report.executables.is_empty() {
+            println!(
+                "Marked {} as executable",
+                report.executables.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", ")
+            );
+        }
+        if !report.overwritten.is_empty() {
+            println!("Overwrote {} existing files:", report.overwritten.len());
+            for path in &report.overwritten {
+                println!("  {}", path.display());
+            }
+        }
+        if !report.skipped.is_empty() {
+            println!("Kept {} existing files:", report.skipped.len());
+            for path in &report.skipped {
+                println!("  {}", path.display());
+            }
+        }
+
+        Ok(project_dir)
+    }
+}
+
+/// Build file endpoint of the Initializr for a project type such as `maven-project` or `gradle-project-kotlin`.
+pub fn build_file_name(project_type: &str) -> &'static str {
+    if project_type.starts_with("maven") {
+        "pom.xml"
+    } else if project_type.contains("kotlin") {
+        "build.gradle.kts"
+    } else {
+        "build.gradle"
+    }
+}
 
-    Ok(())
+fn parse_metadata(server: &str, body: &str) -> Result<InitializrMetadata> {
+    serde_json::from_str::<InitializrMetadata>(body)
+        .with_context(|| format!("Failed to deserialize the metadata fetched from {}", server))
 }
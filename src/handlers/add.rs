@@ -0,0 +1,72 @@
+use std::{fs, path::Path};
+use anyhow::{anyhow, Context, Result};
+use tracing::{debug, info};
+use crate::{
+    client::spring_initializr::SpringInitializr,
+    handlers::{
+        build_file::{add_bom, add_dependency, add_repository, detect_boot_version, has_dependency, BuildSystem},
+        resolve::resolve_dependencies,
+    },
+    models::spring::normalize_boot_version,
+};
+
+pub async fn add_dependencies(initializr: &SpringInitializr, project_dir: &Path, ids: &[String], boot_version: Option<String>) -> Result<()> {
+    let (build_system, build_file) = BuildSystem::detect(project_dir)?;
+    debug!("Detected {} build file at {:?}", build_system, build_file);
+    let mut contents = fs::read_to_string(&build_file)
+        .with_context(|| format!("Failed to read {:?}", build_file))?;
+
+    let boot_version = boot_version
+        .or_else(|| detect_boot_version(build_system, &contents))
+        .map(|boot_version| normalize_boot_version(&boot_version))
+        .ok_or_else(|| anyhow!("Could not detect the Spring Boot version from {:?}, please pass it with '--boot-version'", build_file))?;
+    debug!("Using Spring Boot version {}", boot_version);
+
+    let spring_metadata = initializr.get_metadata().await
+        .with_context(|| "Failed to get the metadata")?;
+    let ids = resolve_dependencies(&ids.join(","), &spring_metadata.dependencies.values, &boot_version)?;
+
+    let details = initializr.get_dependency_details(&boot_version).await?;
+    let mut added = Vec::new();
+    for id in ids.split(',').filter(|id| !id.is_empty()) {
+        let coordinates = details.dependencies.get(id)
+            .ok_or_else(|| anyhow!("{} did not return coordinates for '{}' with Spring Boot {}", initializr.server(), id, boot_version))?;
+
+        if has_dependency(build_system, &contents, coordinates) {
+            println!("{}:{} is already declared in {}, skipping", coordinates.group_id, coordinates.artifact_id, build_system.file_name());
+            continue;
+        }
+
+        if let Some(bom_id) = &coordinates.bom {
+            let bom = details.boms.get(bom_id)
+                .ok_or_else(|| anyhow!("'{}' requires the unknown BOM '{}'", id, bom_id))?;
+            contents = add_bom(build_system, &contents, bom)?;
+
+            for repository_id in &bom.repositories {
+                if let Some(repository) = details.repositories.get(repository_id) {
+                    contents = add_repository(build_system, &contents, repository_id, repository)?;
+                }
+            }
+        }
+        if let Some(repository_id) = &coordinates.repository
+            && let Some(repository) = details.repositories.get(repository_id)
+        {
+            contents = add_repository(build_system, &contents, repository_id, repository)?;
+        }
+
+        contents = add_dependency(build_system, &contents, coordinates)?;
+        added.push(format!("{}:{}", coordinates.group_id, coordinates.artifact_id));
+    }
+
+    if added.is_empty() {
+        println!("Nothing to add to {:?}", build_file);
+        return Ok(());
+    }
+
+    fs::write(&build_file, contents)
+        .with_context(|| format!("Failed to write {:?}", build_file))?;
+    info!("Added {} to {:?}", added.join(", "), build_file);
+    println!("Added {} to {:?}", added.join(", "), build_file);
+
+    Ok(())
+}
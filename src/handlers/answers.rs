@@ -0,0 +1,134 @@
+use std::{fs, path::Path};
+use anyhow::{anyhow, Context, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::{debug, info};
+use crate::models::{answers::Answers, spring::QueryParam};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl FileFormat {
+    pub fn from_path(path: &Path) -> Result<FileFormat> {
+        let extension = path.extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("toml") => Ok(FileFormat::Toml),
+            Some("json") => Ok(FileFormat::Json),
+            Some("yaml") | Some("yml") => Ok(FileFormat::Yaml),
+            _ => Err(anyhow!("Unsupported file format for {:?}, expected a .toml, .json, .yaml or .yml file", path)),
+        }
+    }
+}
+
+pub fn read_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let format = FileFormat::from_path(path)?;
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {:?}", path))?;
+
+    let value = match format {
+        FileFormat::Toml => toml::from_str::<T>(&contents)?,
+        FileFormat::Json => serde_json::from_str::<T>(&contents)?,
+        FileFormat::Yaml => serde_norway::from_str::<T>(&contents)?,
+    };
+
+    Ok(value)
+}
+
+pub fn write_file<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let contents = match FileFormat::from_path(path)? {
+        FileFormat::Toml => toml::to_string_pretty(value)?,
+        FileFormat::Json => serde_json::to_string_pretty(value)?,
+        FileFormat::Yaml => serde_norway::to_string(value)?,
+    };
+
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create the directory {:?}", parent))?;
+    }
+    fs::write(path, contents)
+        .with_context(|| format!("Failed to write {:?}", path))?;
+
+    Ok(())
+}
+
+pub fn load_answers(path: &Path) -> Result<Answers> {
+    let answers = read_file::<Answers>(path)
+        .with_context(|| format!("Failed to load the answers file {:?}", path))?;
+    debug!("Loaded answers from {:?}: {:?}", path, answers);
+
+    Ok(answers)
+}
+
+pub fn save_answers(path: &Path, params: &QueryParam) -> Result<()> {
+    write_file(path, params)
+        .with_context(|| format!("Failed to save the answers file {:?}", path))?;
+    info!("Saved answers to {:?}", path);
+    println!("Saved answers to {:?}", path);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query_params() -> QueryParam {
+        QueryParam {
+            project_type: "maven-project".to_string(),
+            language: "java".to_string(),
+            boot_version: "3.5.6".to_string(),
+            group_id: "com.example".to_string(),
+            artifact_id: "demo".to_string(),
+            name: "demo".to_string(),
+            description: "Demo project: \"quoted\", with # and 'quotes'".to_string(),
+            package_name: Some("com.example.demo".to_string()),
+            packaging: "jar".to_string(),
+            java_version: "21".to_string(),
+            dependencies: "web,actuator".to_string(),
+            base_dir: "demo".to_string(),
+        }
+    }
+
+    #[test]
+    fn saved_answers_load_back_in_every_format() {
+        let temp = tempfile::tempdir().unwrap();
+
+        for name in ["answers.toml", "answers.json", "answers.yaml", "answers.YML"] {
+            let path = temp.path().join("nested").join(name);
+            save_answers(&path, &query_params()).unwrap();
+            let answers = load_answers(&path).unwrap();
+
+            assert_eq!(answers.project_type.as_deref(), Some("maven-project"), "{}", name);
+            assert_eq!(answers.boot_version.as_deref(), Some("3.5.6"), "{}", name);
+            assert_eq!(answers.description.as_deref(), Some("Demo project: \"quoted\", with # and 'quotes'"), "{}", name);
+            assert_eq!(answers.package_name.as_deref(), Some("com.example.demo"), "{}", name);
+            assert_eq!(answers.dependencies.as_deref(), Some("web,actuator"), "{}", name);
+            assert_eq!(answers.base_dir.as_deref(), Some("demo"), "{}", name);
+        }
+    }
+
+    #[test]
+    fn unknown_extensions_are_rejected() {
+        assert!(FileFormat::from_path(Path::new("answers.ini")).is_err());
+        assert!(FileFormat::from_path(Path::new("answers")).is_err());
+        assert_eq!(FileFormat::from_path(Path::new("answers.Yml")).unwrap(), FileFormat::Yaml);
+    }
+
+    #[test]
+    fn partial_files_leave_the_rest_unset() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("answers.yaml");
+        fs::write(&path, "javaVersion: '17'\ndependencies: web\n").unwrap();
+
+        let answers = load_answers(&path).unwrap();
+        assert_eq!(answers.java_version.as_deref(), Some("17"));
+        assert_eq!(answers.dependencies.as_deref(), Some("web"));
+        assert_eq!(answers.group_id, None);
+    }
+}
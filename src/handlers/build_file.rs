@@ -0,0 +1,765 @@
+use std::{fmt, path::{Path, PathBuf}};
+use anyhow::{anyhow, Result};
+use crate::models::spring::{Bom, Coordinates, Repository};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildSystem {
+    Maven,
+    GradleGroovy,
+    GradleKotlin,
+}
+
+impl BuildSystem {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            BuildSystem::Maven => "pom.xml",
+            BuildSystem::GradleGroovy => "build.gradle",
+            BuildSystem::GradleKotlin => "build.gradle.kts",
+        }
+    }
+
+    /// Looks for a build file in `project_dir`, preferring Maven, then Gradle's Kotlin and Groovy DSLs.
+    pub fn detect(project_dir: &Path) -> Result<(BuildSystem, PathBuf)> {
+        [BuildSystem::Maven, BuildSystem::GradleKotlin, BuildSystem::GradleGroovy]
+            .into_iter()
+            .map(|build_system| (build_system, project_dir.join(build_system.file_name())))
+            .find(|(_, path)| path.is_file())
+            .ok_or_else(|| anyhow!("No pom.xml, build.gradle or build.gradle.kts found in {:?}", project_dir))
+    }
+}
+
+impl fmt::Display for BuildSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildSystem::Maven => write!(f, "Maven"),
+            BuildSystem::GradleGroovy => write!(f, "Gradle (Groovy DSL)"),
+            BuildSystem::GradleKotlin => write!(f, "Gradle (Kotlin DSL)"),
+        }
+    }
+}
+
+/// Reads the Spring Boot version from the starter parent of a pom or the boot plugin of a Gradle build.
+pub fn detect_boot_version(build_system: BuildSystem, contents: &str) -> Option<String> {
+    match build_system {
+        BuildSystem::Maven => {
+            let parent = find_element(contents, &["project", "parent"])?;
+            let parent = &contents[parent.content_start..parent.content_end];
+            if !parent.contains("spring-boot-starter-parent") {
+                return None;
+            }
+            element_text(parent, "version")
+        }
+        BuildSystem::GradleGroovy | BuildSystem::GradleKotlin => contents.lines()
+            .find(|line| line.contains("org.springframework.boot") && line.contains("version"))
+            .and_then(|line| {
+                let (_, version) = line.split_once("version")?;
+                let quote = version.find(['\'', '"'])?;
+                let version = &version[quote + 1..];
+                let end = version.find(['\'', '"'])?;
+                Some(version[..end].to_string())
+            }),
+    }
+}
+
+/// Whether `coordinates` is already declared in the build file.
+pub fn has_dependency(build_system: BuildSystem, contents: &str, coordinates: &Coordinates) -> bool {
+    match build_system {
+        BuildSystem::Maven => {
+            let Some(dependencies) = find_element(contents, &["project", "dependencies"]) else {
+                return false;
+            };
+            contents[dependencies.content_start..dependencies.content_end]
+                .split("<dependency>")
+                .any(|dependency| {
+                    element_text(dependency, "groupId").as_deref() == Some(coordinates.group_id.as_str())
+                        && element_text(dependency, "artifactId").as_deref() == Some(coordinates.artifact_id.as_str())
+                })
+        }
+        BuildSystem::GradleGroovy | BuildSystem::GradleKotlin => {
+            // The artifact id has to end right there, web must not match webflux
+            let notation = format!("{}:{}", coordinates.group_id, coordinates.artifact_id);
+            ['\'', '"'].into_iter().any(|quote| {
+                let quoted = format!("{}{}", quote, notation);
+                contents.match_indices(&quoted)
+                    .any(|(index, _)| contents[index + quoted.len()..].starts_with([quote, ':']))
+            })
+        }
+    }
+}
+
+pub fn add_dependency(build_system: BuildSystem, contents: &str, coordinates: &Coordinates) -> Result<String> {
+    match build_system {
+        BuildSystem::Maven => maven_add_dependency(contents, coordinates),
+        BuildSystem::GradleGroovy | BuildSystem::GradleKotlin => gradle_add_dependency(build_system, contents, coordinates),
+    }
+}
+
+pub fn add_bom(build_system: BuildSystem, contents: &str, bom: &Bom) -> Result<String> {
+    match build_system {
+        BuildSystem::Maven => maven_add_bom(contents, bom),
+        BuildSystem::GradleGroovy | BuildSystem::GradleKotlin => gradle_add_bom(build_system, contents, bom),
+    }
+}
+
+pub fn add_repository(build_system: BuildSystem, contents: &str, id: &str, repository: &Repository) -> Result<String> {
+    match build_system {
+        BuildSystem::Maven => maven_add_repository(contents, id, repository),
+        BuildSystem::GradleGroovy | BuildSystem::GradleKotlin => gradle_add_repository(build_system, contents, repository),
+    }
+}
+
+fn indent_unit(contents: &str) -> &'static str {
+    if contents.contains("\n\t") { "\t" } else { "    " }
+}
+
+/// Returns the index of the start of the line containing `index` and that line's leading whitespace.
+fn line_indent(contents: &str, index: usize) -> (usize, String) {
+    let line_start = contents[..index].rfind('\n').map(|newline| newline + 1).unwrap_or(0);
+    let indent = contents[line_start..index]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect::<String>();
+
+    (line_start, indent)
+}
+
+fn indent_lines(lines: &[String], indent: &str) -> String {
+    lines.iter()
+        .map(|line| format!("{}{}\n", indent, line))
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Element {
+    content_start: usize,
+    content_end: usize,
+}
+
+/// Minimal XML walker that finds the first element at exactly `path`, e.g. `["project", "dependencies"]`,
+/// so the project dependencies are not confused with the ones in dependencyManagement or plugins.
+fn find_element(xml: &str, path: &[&str]) -> Option<Element> {
+    let mut stack: Vec<(&str, usize)> = Vec::new();
+    let mut index = 0;
+
+    while let Some(offset) = xml[index..].find('<') {
+        let start = index + offset;
+        let rest = &xml[start..];
+
+        if rest.starts_with("<!--") {
+            index = start + rest.find("-->")? + 3;
+            continue;
+        }
+        if rest.starts_with("<?") || rest.starts_with("<!") {
+            index = start + rest.find('>')? + 1;
+            continue;
+        }
+
+        let end = start + rest.find('>')?;
+        let tag = &xml[start + 1..end];
+        index = end + 1;
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            if path.last() == Some(&name) && stack.iter().map(|(open, _)| *open).eq(path.iter().copied()) {
+                let (_, content_start) = stack[stack.len() - 1];
+                return Some(Element { content_start, content_end: start });
+            }
+            if let Some(position) = stack.iter().rposition(|(open, _)| *open == name) {
+                stack.truncate(position);
+            }
+            continue;
+        }
+        if tag.ends_with('/') {
+            continue;
+        }
+
+        let name = tag.split_whitespace().next().unwrap_or("");
+        stack.push((name, end + 1));
+    }
+
+    None
+}
+
+fn element_text(xml: &str, name: &str) -> Option<String> {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+    let start = xml.find(&open)? + open.len();
+    let end = start + xml[start..].find(&close)?;
+
+    Some(xml[start..end].trim().to_string())
+}
+
+/// Inserts `lines` as children right before the closing tag of the element at `path`, or, when the
+/// element does not exist yet, wraps them in a new element placed before `fallback_before`.
+fn maven_insert(contents: &str, path: &[&str], lines: Vec<String>, fallback_before: &[&[&str]]) -> Result<String> {
+    let unit = indent_unit(contents);
+
+    if let Some(element) = find_element(contents, path) {
+        let (line_start, indent) = line_indent(contents, element.content_end);
+        let closes_on_own_line = contents[line_start..element.content_end].trim().is_empty();
+        let insert_at = if closes_on_own_line { line_start } else { element.content_end };
+        let mut block = indent_lines(&lines, &format!("{}{}", indent, unit));
+        if !closes_on_own_line {
+            block = format!("\n{}{}", block, indent);
+        }
+
+        return Ok(format!("{}{}{}", &contents[..insert_at], block, &contents[insert_at..]));
+    }
+
+    let Some((name, parent)) = path.split_last() else {
+        return Err(anyhow!("Could not find where to insert into the pom.xml"));
+    };
+    // The root element itself is missing or the pom is not parseable
+    if parent.is_empty() {
+        return Err(anyhow!("Could not find <{}> in the pom.xml", name));
+    }
+    let mut wrapped = vec![format!("<{}>", name)];
+    wrapped.extend(lines.into_iter().map(|line| format!("{}{}", unit, line)));
+    wrapped.push(format!("</{}>", name));
+
+    let anchor = fallback_before.iter()
+        .filter_map(|sibling| find_element(contents, sibling).map(|element| (sibling, element)))
+        .map(|(sibling, element)| {
+            let open = format!("<{}", sibling[sibling.len() - 1]);
+            contents[..element.content_start].rfind(&open).unwrap_or(element.content_start)
+        })
+        .next();
+
+    match anchor {
+        Some(anchor) => {
+            let (line_start, indent) = line_indent(contents, anchor);
+            let block = indent_lines(&wrapped, &indent);
+            Ok(format!("{}{}{}", &contents[..line_start], block, &contents[line_start..]))
+        }
+        None => maven_insert(contents, parent, wrapped, &[]),
+    }
+}
+
+fn maven_dependency_lines(coordinates: &Coordinates) -> Vec<String> {
+    let mut lines = vec![
+        "<dependency>".to_string(),
+        format!("\t<groupId>{}</groupId>", coordinates.group_id),
+        format!("\t<artifactId>{}</artifactId>", coordinates.artifact_id),
+    ];
+    if let Some(version) = &coordinates.version {
+        lines.push(format!("\t<version>{}</version>", version));
+    }
+    match coordinates.scope.as_deref() {
+        Some("runtime") => {
+            lines.push("\t<scope>runtime</scope>".to_string());
+            if coordinates.artifact_id == "spring-boot-devtools" {
+                lines.push("\t<optional>true</optional>".to_string());
+            }
+        }
+        Some("test") => lines.push("\t<scope>test</scope>".to_string()),
+        Some("provided") => lines.push("\t<scope>provided</scope>".to_string()),
+        Some("annotationProcessor") | Some("compileOnly") => lines.push("\t<optional>true</optional>".to_string()),
+        _ => {}
+    }
+    lines.push("</dependency>".to_string());
+
+    lines
+}
+
+fn retab(lines: Vec<String>, unit: &str) -> Vec<String> {
+    lines.into_iter()
+        .map(|line| {
+            let tabs = line.chars().take_while(|c| *c == '\t').count();
+            format!("{}{}", unit.repeat(tabs), &line[tabs..])
+        })
+        .collect()
+}
+
+fn maven_add_dependency(contents: &str, coordinates: &Coordinates) -> Result<String> {
+    let lines = retab(maven_dependency_lines(coordinates), indent_unit(contents));
+    maven_insert(contents, &["project", "dependencies"], lines, &[&["project", "dependencyManagement"], &["project", "build"]])
+}
+
+fn maven_add_bom(contents: &str, bom: &Bom) -> Result<String> {
+    let already_imported = find_element(contents, &["project", "dependencyManagement", "dependencies"])
+        .map(|element| contents[element.content_start..element.content_end].contains(&format!("<artifactId>{}</artifactId>", bom.artifact_id)))
+        .unwrap_or(false);
+    if already_imported {
+        return Ok(contents.to_string());
+    }
+
+    let lines = retab(vec![
+        "<dependency>".to_string(),
+        format!("\t<groupId>{}</groupId>", bom.group_id),
+        format!("\t<artifactId>{}</artifactId>", bom.artifact_id),
+        format!("\t<version>{}</version>", bom.version),
+        "\t<type>pom</type>".to_string(),
+        "\t<scope>import</scope>".to_string(),
+        "</dependency>".to_string(),
+    ], indent_unit(contents));
+
+    if find_element(contents, &["project", "dependencyManagement"]).is_some() {
+        return maven_insert(contents, &["project", "dependencyManagement", "dependencies"], lines, &[]);
+    }
+
+    let unit = indent_unit(contents);
+    let mut wrapped = vec!["<dependencies>".to_string()];
+    wrapped.extend(lines.into_iter().map(|line| format!("{}{}", unit, line)));
+    wrapped.push("</dependencies>".to_string());
+    maven_insert(contents, &["project", "dependencyManagement"], wrapped, &[&["project", "build"]])
+}
+
+fn maven_add_repository(contents: &str, id: &str, repository: &Repository) -> Result<String> {
+    if contents.contains(&format!("<url>{}</url>", repository.url)) {
+        return Ok(contents.to_string());
+    }
+
+    let lines = retab(vec![
+        "<repository>".to_string(),
+        format!("\t<id>{}</id>", id),
+        format!("\t<name>{}</name>", repository.name),
+        format!("\t<url>{}</url>", repository.url),
+        "\t<snapshots>".to_string(),
+        format!("\t\t<enabled>{}</enabled>", repository.snapshot_enabled),
+        "\t</snapshots>".to_string(),
+        "</repository>".to_string(),
+    ], indent_unit(contents));
+
+    maven_insert(contents, &["project", "repositories"], lines, &[])
+}
+
+/// Finds a `name { ... }` block at the top level of `script`, skipping strings and comments.
+/// Returns the indices of the opening and the matching closing brace.
+fn find_block(script: &str, name: &str) -> Option<(usize, usize)> {
+    let bytes = script.as_bytes();
+    let mut depth = 0usize;
+    let mut open: Option<usize> = None;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let rest = &script[index..];
+        match bytes[index] {
+            b'/' if rest.starts_with("//") => {
+                index += rest.find('\n').unwrap_or(rest.len());
+                continue;
+            }
+            b'/' if rest.starts_with("/*") => {
+                index += rest.find("*/").map(|end| end + 2).unwrap_or(rest.len());
+                continue;
+            }
+            quote @ (b'\'' | b'"') => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != quote {
+                    if bytes[index] == b'\\' {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+            }
+            b'{' => {
+                if depth == 0 && open.is_none() {
+                    let before = script[..index].trim_end();
+                    let is_word_boundary = before.strip_suffix(name)
+                        .is_some_and(|prefix| !prefix.ends_with(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '.'));
+                    if is_word_boundary {
+                        open = Some(index);
+                    }
+                }
+                depth += 1;
+            }
+            b'}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 && let Some(open) = open {
+                    return Some((open, index));
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    None
+}
+
+/// Inserts `lines` at the end of the top level block `name`, appending a new block when it is missing.
+fn gradle_insert(contents: &str, name: &str, lines: &[String]) -> String {
+    match find_block(contents, name) {
+        Some((_, close)) => insert_before_brace(contents, close, lines),
+        None => {
+            let separator = if contents.ends_with('\n') { "\n" } else { "\n\n" };
+            format!("{}{}{} {{\n{}}}\n", contents, separator, name, indent_lines(lines, indent_unit(contents)))
+        }
+    }
+}
+
+fn insert_before_brace(contents: &str, close: usize, lines: &[String]) -> String {
+    let (line_start, indent) = line_indent(contents, close);
+    let block = indent_lines(lines, &format!("{}{}", indent, indent_unit(contents)));
+    if contents[line_start..close].trim().is_empty() {
+        format!("{}{}{}", &contents[..line_start], block, &contents[line_start..])
+    } else {
+        format!("{}\n{}{}{}", &contents[..close], block, indent, &contents[close..])
+    }
+}
+
+fn gradle_configurations(coordinates: &Coordinates) -> Vec<&'static str> {
+    if coordinates.artifact_id == "spring-boot-devtools" {
+        return vec!["developmentOnly"];
+    }
+
+    match coordinates.scope.as_deref() {
+        Some("runtime") => vec!["runtimeOnly"],
+        Some("test") => vec!["testImplementation"],
+        Some("provided") | Some("compileOnly") => vec!["compileOnly"],
+        Some("annotationProcessor") => vec!["compileOnly", "annotationProcessor"],
+        _ => vec!["implementation"],
+    }
+}
+
+fn gradle_add_dependency(build_system: BuildSystem, contents: &str, coordinates: &Coordinates) -> Result<String> {
+    let notation = match &coordinates.version {
+        Some(version) => format!("{}:{}:{}", coordinates.group_id, coordinates.artifact_id, version),
+        None => format!("{}:{}", coordinates.group_id, coordinates.artifact_id),
+    };
+    let lines = gradle_configurations(coordinates).into_iter()
+        .map(|configuration| match build_system {
+            BuildSystem::GradleKotlin => format!("{}(\"{}\")", configuration, notation),
+            _ => format!("{} '{}'", configuration, notation),
+        })
+        .collect::<Vec<_>>();
+
+    Ok(gradle_insert(contents, "dependencies", &lines))
+}
+
+fn gradle_add_bom(build_system: BuildSystem, contents: &str, bom: &Bom) -> Result<String> {
+    let notation = format!("{}:{}:{}", bom.group_id, bom.artifact_id, bom.version);
+    if contents.contains(&format!("{}:{}:", bom.group_id, bom.artifact_id)) {
+        return Ok(contents.to_string());
+    }
+    let line = match build_system {
+        BuildSystem::GradleKotlin => format!("mavenBom(\"{}\")", notation),
+        _ => format!("mavenBom \"{}\"", notation),
+    };
+
+    let Some((open, close)) = find_block(contents, "dependencyManagement") else {
+        let unit = indent_unit(contents);
+        return Ok(gradle_insert(contents, "dependencyManagement", &[
+            "imports {".to_string(),
+            format!("{}{}", unit, line),
+            "}".to_string(),
+        ]));
+    };
+
+    match find_block(&contents[open + 1..close], "imports") {
+        Some((_, imports_close)) => Ok(insert_before_brace(contents, open + 1 + imports_close, &[line])),
+        None => Err(anyhow!("Found a dependencyManagement block without an imports block, please add the BOM {} manually", notation)),
+    }
+}
+
+fn gradle_add_repository(build_system: BuildSystem, contents: &str, repository: &Repository) -> Result<String> {
+    if contents.contains(&repository.url) {
+        return Ok(contents.to_string());
+    }
+
+    let line = match build_system {
+        BuildSystem::GradleKotlin => format!("maven {{ url = uri(\"{}\") }}", repository.url),
+        _ => format!("maven {{ url '{}' }}", repository.url),
+    };
+
+    Ok(gradle_insert(contents, "repositories", &[line]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POM: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+<project>\n\
+\t<modelVersion>4.0.0</modelVersion>\n\
+\t<parent>\n\
+\t\t<groupId>org.springframework.boot</groupId>\n\
+\t\t<artifactId>spring-boot-starter-parent</artifactId>\n\
+\t\t<version>3.5.6</version>\n\
+\t</parent>\n\
+\t<dependencies>\n\
+\t\t<dependency>\n\
+\t\t\t<groupId>org.springframework.boot</groupId>\n\
+\t\t\t<artifactId>spring-boot-starter-webflux</artifactId>\n\
+\t\t</dependency>\n\
+\t</dependencies>\n\
+\t<build>\n\
+\t\t<plugins>\n\
+\t\t\t<plugin>\n\
+\t\t\t\t<groupId>org.springframework.boot</groupId>\n\
+\t\t\t\t<artifactId>spring-boot-maven-plugin</artifactId>\n\
+\t\t\t</plugin>\n\
+\t\t</plugins>\n\
+\t</build>\n\
+</project>\n";
+
+    const GROOVY: &str = "plugins {\n\
+\tid 'java'\n\
+\tid 'org.springframework.boot' version '3.5.6'\n\
+}\n\
+\n\
+repositories {\n\
+\tmavenCentral()\n\
+}\n\
+\n\
+dependencies {\n\
+\timplementation 'org.springframework.boot:spring-boot-starter'\n\
+}\n";
+
+    const KOTLIN: &str = r#"plugins {
+    java
+    id("org.springframework.boot") version "3.5.6"
+}
+
+dependencies {
+    implementation("org.springframework.boot:spring-boot-starter")
+}
+"#;
+
+    fn spaces(xml: &str) -> String {
+        xml.replace('\t', "    ")
+    }
+
+    fn bom() -> Bom {
+        Bom {
+            group_id: "org.springframework.cloud".to_string(),
+            artifact_id: "spring-cloud-dependencies".to_string(),
+            version: "2025.0.0".to_string(),
+            repositories: Vec::new(),
+        }
+    }
+
+    fn starter(artifact_id: &str) -> Coordinates {
+        Coordinates {
+            group_id: "org.springframework.boot".to_string(),
+            artifact_id: artifact_id.to_string(),
+            version: None,
+            scope: None,
+            bom: None,
+            repository: None,
+        }
+    }
+
+    #[test]
+    fn gradle_has_dependency_does_not_match_prefixes() {
+        let groovy = "dependencies {\n\timplementation 'org.springframework.boot:spring-boot-starter-webflux'\n}\n";
+        let kotlin = "dependencies {\n    implementation(\"org.springframework.boot:spring-boot-starter-webflux\")\n}\n";
+
+        assert!(!has_dependency(BuildSystem::GradleGroovy, groovy, &starter("spring-boot-starter-web")));
+        assert!(!has_dependency(BuildSystem::GradleKotlin, kotlin, &starter("spring-boot-starter-web")));
+        assert!(has_dependency(BuildSystem::GradleGroovy, groovy, &starter("spring-boot-starter-webflux")));
+        assert!(has_dependency(BuildSystem::GradleKotlin, kotlin, &starter("spring-boot-starter-webflux")));
+    }
+
+    #[test]
+    fn maven_without_project_is_an_error() {
+        let error = add_dependency(BuildSystem::Maven, "<notproject></notproject>", &starter("spring-boot-starter-web")).unwrap_err();
+        assert_eq!(error.to_string(), "Could not find <project> in the pom.xml");
+
+        assert!(add_dependency(BuildSystem::Maven, "", &starter("spring-boot-starter-web")).is_err());
+        assert!(add_dependency(BuildSystem::Maven, "<project>", &starter("spring-boot-starter-web")).is_err());
+    }
+
+    #[test]
+    fn gradle_has_dependency_matches_versioned_notation() {
+        let groovy = "dependencies {\n\timplementation \"org.springframework.boot:spring-boot-starter-web:3.5.6\"\n}\n";
+
+        assert!(has_dependency(BuildSystem::GradleGroovy, groovy, &starter("spring-boot-starter-web")));
+    }
+
+    #[test]
+    fn find_element_matches_the_exact_path() {
+        let dependencies = find_element(POM, &["project", "dependencies"]).unwrap();
+        let dependencies = &POM[dependencies.content_start..dependencies.content_end];
+        assert!(dependencies.contains("spring-boot-starter-webflux"));
+        assert!(!dependencies.contains("spring-boot-maven-plugin"));
+
+        // Only the parent has a groupId here
+        assert!(find_element(POM, &["project", "groupId"]).is_none());
+        assert!(find_element(POM, &["project", "repositories"]).is_none());
+    }
+
+    #[test]
+    fn find_element_skips_comments_and_dependency_management() {
+        let xml = "<project>\n\
+<!-- <dependencies><dependency>commented</dependency></dependencies> -->\n\
+<dependencyManagement><dependencies><dependency>managed</dependency></dependencies></dependencyManagement>\n\
+<dependencies><dependency>declared</dependency></dependencies>\n\
+</project>\n";
+
+        let dependencies = find_element(xml, &["project", "dependencies"]).unwrap();
+        assert_eq!(&xml[dependencies.content_start..dependencies.content_end], "<dependency>declared</dependency>");
+        let managed = find_element(xml, &["project", "dependencyManagement", "dependencies"]).unwrap();
+        assert_eq!(&xml[managed.content_start..managed.content_end], "<dependency>managed</dependency>");
+    }
+
+    #[test]
+    fn find_block_finds_top_level_blocks_only() {
+        let (open, close) = find_block(GROOVY, "dependencies").unwrap();
+        assert_eq!(GROOVY[open + 1..close].trim(), "implementation 'org.springframework.boot:spring-boot-starter'");
+
+        let nested = "buildscript {\n\tdependencies {\n\t\tclasspath 'a:b:1'\n\t}\n}\n";
+        assert!(find_block(nested, "dependencies").is_none());
+        assert!(find_block("testDependencies {\n}\n", "dependencies").is_none());
+        assert!(find_block("ext.dependencies {\n}\n", "dependencies").is_none());
+    }
+
+    #[test]
+    fn find_block_skips_strings_and_comments() {
+        let script = "// dependencies {\n/* dependencies { */\nversion = '}'\ndependencies {\n\timplementation \"a:b\" // }\n}\n";
+
+        let (open, close) = find_block(script, "dependencies").unwrap();
+        assert_eq!(&script[open..=close], "{\n\timplementation \"a:b\" // }\n}");
+    }
+
+    #[test]
+    fn maven_has_dependency_checks_whole_ids_of_project_dependencies() {
+        assert!(has_dependency(BuildSystem::Maven, POM, &starter("spring-boot-starter-webflux")));
+        assert!(!has_dependency(BuildSystem::Maven, POM, &starter("spring-boot-starter-web")));
+        // The plugin and the parent are not dependencies
+        assert!(!has_dependency(BuildSystem::Maven, POM, &starter("spring-boot-maven-plugin")));
+        assert!(!has_dependency(BuildSystem::Maven, POM, &starter("spring-boot-starter-parent")));
+    }
+
+    #[test]
+    fn maven_add_dependency_keeps_tabs_and_spaces() {
+        let added = "\t\t<dependency>\n\
+\t\t\t<groupId>org.springframework.boot</groupId>\n\
+\t\t\t<artifactId>spring-boot-starter-web</artifactId>\n\
+\t\t</dependency>\n";
+        let expected = POM.replace("\t</dependencies>", &format!("{}\t</dependencies>", added));
+
+        let with_tabs = add_dependency(BuildSystem::Maven, POM, &starter("spring-boot-starter-web")).unwrap();
+        assert_eq!(with_tabs, expected);
+        let with_spaces = add_dependency(BuildSystem::Maven, &spaces(POM), &starter("spring-boot-starter-web")).unwrap();
+        assert_eq!(with_spaces, spaces(&expected));
+        assert!(has_dependency(BuildSystem::Maven, &with_spaces, &starter("spring-boot-starter-web")));
+    }
+
+    #[test]
+    fn maven_add_dependency_writes_the_scope() {
+        let mut test = starter("spring-boot-starter-test");
+        test.scope = Some("test".to_string());
+
+        let pom = add_dependency(BuildSystem::Maven, POM, &test).unwrap();
+        assert!(pom.contains("\t\t\t<artifactId>spring-boot-starter-test</artifactId>\n\t\t\t<scope>test</scope>\n\t\t</dependency>\n\t</dependencies>"));
+    }
+
+    #[test]
+    fn maven_add_dependency_creates_missing_dependencies() {
+        let without_dependencies = {
+            let start = POM.find("\t<dependencies>").unwrap();
+            let end = POM.find("\t<build>").unwrap();
+            format!("{}{}", &POM[..start], &POM[end..])
+        };
+        let added = "\t<dependencies>\n\
+\t\t<dependency>\n\
+\t\t\t<groupId>org.springframework.boot</groupId>\n\
+\t\t\t<artifactId>spring-boot-starter-web</artifactId>\n\
+\t\t</dependency>\n\
+\t</dependencies>\n";
+
+        let pom = add_dependency(BuildSystem::Maven, &without_dependencies, &starter("spring-boot-starter-web")).unwrap();
+        assert_eq!(pom, without_dependencies.replace("\t<build>", &format!("{}\t<build>", added)));
+
+        // Without a build section either, the dependencies go at the end of the project
+        let bare = "<project>\n    <modelVersion>4.0.0</modelVersion>\n</project>\n";
+        let pom = add_dependency(BuildSystem::Maven, bare, &starter("spring-boot-starter-web")).unwrap();
+        assert_eq!(pom, format!("<project>\n    <modelVersion>4.0.0</modelVersion>\n{}</project>\n", spaces(added)));
+    }
+
+    #[test]
+    fn maven_add_bom_creates_dependency_management() {
+        let added = "\t<dependencyManagement>\n\
+\t\t<dependencies>\n\
+\t\t\t<dependency>\n\
+\t\t\t\t<groupId>org.springframework.cloud</groupId>\n\
+\t\t\t\t<artifactId>spring-cloud-dependencies</artifactId>\n\
+\t\t\t\t<version>2025.0.0</version>\n\
+\t\t\t\t<type>pom</type>\n\
+\t\t\t\t<scope>import</scope>\n\
+\t\t\t</dependency>\n\
+\t\t</dependencies>\n\
+\t</dependencyManagement>\n";
+        let expected = POM.replace("\t<build>", &format!("{}\t<build>", added));
+
+        let pom = add_bom(BuildSystem::Maven, POM, &bom()).unwrap();
+        assert_eq!(pom, expected);
+        assert_eq!(add_bom(BuildSystem::Maven, &pom, &bom()).unwrap(), expected);
+        assert_eq!(add_bom(BuildSystem::Maven, &spaces(POM), &bom()).unwrap(), spaces(&expected));
+    }
+
+    #[test]
+    fn maven_add_bom_extends_existing_dependency_management() {
+        let managed = "\t<dependencyManagement>\n\
+\t\t<dependencies>\n\
+\t\t\t<dependency>\n\
+\t\t\t\t<groupId>com.example</groupId>\n\
+\t\t\t\t<artifactId>example-bom</artifactId>\n\
+\t\t\t</dependency>\n\
+\t\t</dependencies>\n\
+\t</dependencyManagement>\n";
+        let pom = POM.replace("\t<build>", &format!("{}\t<build>", managed));
+
+        let pom = add_bom(BuildSystem::Maven, &pom, &bom()).unwrap();
+        let managed = find_element(&pom, &["project", "dependencyManagement", "dependencies"]).unwrap();
+        let managed = &pom[managed.content_start..managed.content_end];
+        assert!(managed.contains("<artifactId>example-bom</artifactId>"));
+        assert!(managed.contains("\t\t\t\t<artifactId>spring-cloud-dependencies</artifactId>\n"));
+        assert!(managed.find("example-bom") < managed.find("spring-cloud-dependencies"));
+        // The project dependencies are left alone
+        assert!(!has_dependency(BuildSystem::Maven, &pom, &Coordinates { group_id: bom().group_id, ..starter("spring-cloud-dependencies") }));
+    }
+
+    #[test]
+    fn gradle_add_dependency_appends_to_the_dependencies_block() {
+        let groovy = add_dependency(BuildSystem::GradleGroovy, GROOVY, &starter("spring-boot-starter-web")).unwrap();
+        assert_eq!(groovy, GROOVY.replace(
+            "spring-boot-starter'\n}",
+            "spring-boot-starter'\n\timplementation 'org.springframework.boot:spring-boot-starter-web'\n}",
+        ));
+
+        let kotlin = add_dependency(BuildSystem::GradleKotlin, KOTLIN, &starter("spring-boot-starter-web")).unwrap();
+        assert_eq!(kotlin, KOTLIN.replace(
+            "spring-boot-starter\")\n}",
+            "spring-boot-starter\")\n    implementation(\"org.springframework.boot:spring-boot-starter-web\")\n}",
+        ));
+    }
+
+    #[test]
+    fn gradle_add_dependency_uses_the_configurations_of_the_scope() {
+        let mut lombok = starter("lombok");
+        lombok.group_id = "org.projectlombok".to_string();
+        lombok.scope = Some("annotationProcessor".to_string());
+
+        let kotlin = add_dependency(BuildSystem::GradleKotlin, KOTLIN, &lombok).unwrap();
+        assert!(kotlin.contains("    compileOnly(\"org.projectlombok:lombok\")\n    annotationProcessor(\"org.projectlombok:lombok\")\n}"));
+    }
+
+    #[test]
+    fn gradle_add_dependency_creates_missing_dependencies() {
+        let script = "plugins {\n\tid 'java'\n}\n";
+
+        let groovy = add_dependency(BuildSystem::GradleGroovy, script, &starter("spring-boot-starter-web")).unwrap();
+        assert_eq!(groovy, "plugins {\n\tid 'java'\n}\n\ndependencies {\n\timplementation 'org.springframework.boot:spring-boot-starter-web'\n}\n");
+    }
+
+    #[test]
+    fn gradle_add_bom_creates_dependency_management() {
+        let expected = format!("{}\ndependencyManagement {{\n\timports {{\n\t\tmavenBom \"org.springframework.cloud:spring-cloud-dependencies:2025.0.0\"\n\t}}\n}}\n", GROOVY);
+
+        let groovy = add_bom(BuildSystem::GradleGroovy, GROOVY, &bom()).unwrap();
+        assert_eq!(groovy, expected);
+        assert_eq!(add_bom(BuildSystem::GradleGroovy, &groovy, &bom()).unwrap(), expected);
+    }
+
+    #[test]
+    fn gradle_add_bom_extends_existing_imports() {
+        let kotlin = format!("{}\ndependencyManagement {{\n    imports {{\n        mavenBom(\"com.example:example-bom:1.0.0\")\n    }}\n}}\n", KOTLIN);
+
+        let kotlin = add_bom(BuildSystem::GradleKotlin, &kotlin, &bom()).unwrap();
+        assert!(kotlin.ends_with("        mavenBom(\"com.example:example-bom:1.0.0\")\n        mavenBom(\"org.springframework.cloud:spring-cloud-dependencies:2025.0.0\")\n    }\n}\n"));
+
+        let without_imports = format!("{}\ndependencyManagement {{\n}}\n", KOTLIN);
+        assert!(add_bom(BuildSystem::GradleKotlin, &without_imports, &bom()).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+use std::{env, fs, io::{BufWriter, Write}, iter::once, path::{Path, PathBuf}, process::Command};
+use anyhow::{anyhow, Context, Result};
+use toml_edit::{DocumentMut, Item, Table, TableLike};
+use tracing::debug;
+use crate::{cli::root::OutputFormat, handlers::list::csv_field, models::config::Config};
+
+pub fn config_path() -> Result<PathBuf> {
+    if let Some(path) = env::var_os("XSPRING_CONFIG") {
+        return Ok(PathBuf::from(path));
+    }
+
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| anyhow!("Could not determine the configuration directory for this platform"))?;
+
+    Ok(config_dir.join("xspring").join("config.toml"))
+}
+
+pub fn load_config() -> Result<Config> {
+    let path = config_path()?;
+    if !path.exists() {
+        debug!("No config file found at {:?}, using defaults", path);
+        return Ok(Config::default());
+    }
+
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read the config file at {:?}", path))?;
+    let config = toml::from_str::<Config>(&contents)
+        .with_context(|| format!("Failed to parse the config file at {:?}", path))?;
+    debug!("Loaded config from {:?}: {:?}", path, config);
+
+    Ok(config)
+}
+
+/// Prints the value at a dotted key such as `git.init` or `defaults.groupId`.
+pub fn get_config_value<W: Write>(buf: &mut BufWriter<W>, key: &str) -> Result<()> {
+    let document = read_document(&config_path()?)?;
+    let item = key.split('.')
+        .try_fold(document.as_item(), |item, part| item.get(part))
+        .filter(|item| !item.is_none())
+        .ok_or_else(|| anyhow!("'{}' is not set", key))?;
+
+    match item.as_str() {
+        Some(value) => writeln!(buf, "{}", value)?,
+        None => writeln!(buf, "{}", item.to_string().trim())?,
+    }
+
+    Ok(())
+}
+
+/// Sets a dotted key, keeping the comments and layout of the rest of the file.
+/// The value is read as TOML when that gives a valid config (`true`, `3600`, `["a", "b"]`),
+/// and as a plain string otherwise.
+pub fn set_config_value(key: &str, value: &str) -> Result<()> {
+    let path = config_path()?;
+    let document = read_document(&path)?;
+
+    match with_value(&document, key, value) {
+        Ok(updated) => write_document(&path, &updated),
+        // Older snake_case keys still load, so point at the spelling that is written now
+        Err(err) => match to_camel_case(key) {
+            camel_case if camel_case != key && with_value(&document, &camel_case, value).is_ok() => {
+                Err(anyhow!("Unknown config key '{}', config keys are camelCase: '{}'", key, camel_case))
+            }
+            _ => Err(err),
+        },
+    }
+}
+
+fn with_value(document: &DocumentMut, key: &str, value: &str) -> Result<DocumentMut> {
+    let typed = value.parse::<toml_edit::Value>().ok().filter(|typed| !typed.is_str());
+    let mut last_err = None;
+    for candidate in typed.into_iter().chain(once(toml_edit::Value::from(value))) {
+        let mut updated = document.clone();
+        insert_value(&mut updated, key, candidate)?;
+        match validate_document(&updated, key) {
+            Ok(()) => return Ok(updated),
+            Err(err) => last_err = Some(err),
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| anyhow!("Invalid value '{}' for '{}'", value, key)))
+}
+
+pub fn unset_config_value(key: &str) -> Result<()> {
+    let path = config_path()?;
+    let mut document = read_document(&path)?;
+
+    let (parents, last) = match key.rsplit_once('.') {
+        Some((parents, last)) => (Some(parents), last),
+        None => (None, key),
+    };
+    let table = match parents {
+        Some(parents) => parents.split('.')
+            .try_fold(document.as_item_mut(), |item, part| item.get_mut(part))
+            .and_then(|item| item.as_table_like_mut()),
+        None => Some(document.as_table_mut() as &mut dyn TableLike),
+    };
+    if table.and_then(|table| table.remove(last)).is_none() {
+        return Err(anyhow!("'{}' is not set", key));
+    }
+
+    write_document(&path, &document)
+}
+
+pub fn print_config<W: Write>(buf: &mut BufWriter<W>, format: OutputFormat) -> Result<()> {
+    let config = load_config()?;
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *buf, &config)?;
+            writeln!(buf)?;
+        }
+        OutputFormat::Yaml => serde_norway::to_writer(&mut *buf, &config)?,
+        OutputFormat::Table | OutputFormat::Csv => {
+            if format == OutputFormat::Table {
+                writeln!(buf, "# {}", config_path()?.display())?;
+            }
+            let mut entries = Vec::new();
+            flatten("", &toml::Value::try_from(&config)?, &mut entries);
+            for (key, value) in entries {
+                match format {
+                    OutputFormat::Csv => writeln!(buf, "{},{}", csv_field(&key), csv_field(&value))?,
+                    _ => writeln!(buf, "{} = {}", key, value)?,
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Opens the config file in `$VISUAL` or `$EDITOR` and checks that it still parses afterwards.
+pub fn edit_config() -> Result<()> {
+    let path = config_path()?;
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create the directory {:?}", parent))?;
+        }
+        fs::write(&path, "")
+            .with_context(|| format!("Failed to create the config file at {:?}", path))?;
+    }
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| if cfg!(windows) { "notepad".to_string() } else { "vi".to_string() });
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or_else(|| anyhow!("The editor command is empty"))?;
+    debug!("Editing {:?} with {}", path, editor);
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status()
+        .with_context(|| format!("Failed to start the editor '{}'", editor))?;
+    if !status.success() {
+        return Err(anyhow!("The editor '{}' exited with {}", editor, status));
+    }
+
+    load_config()
+        .with_context(|| format!("The edited config file at {:?} is invalid", path))?;
+
+    Ok(())
+}
+
+fn read_document(path: &Path) -> Result<DocumentMut> {
+    if !path.exists() {
+        return Ok(DocumentMut::new());
+    }
+
+    fs::read_to_string(path)
+        .with_context(|| format!("Failed to read the config file at {:?}", path))?
+        .parse::<DocumentMut>()
+        .with_context(|| format!("Failed to parse the config file at {:?}", path))
+}
+
+fn write_document(path: &Path, document: &DocumentMut) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create the directory {:?}", parent))?;
+    }
+    fs::write(path, document.to_string())
+        .with_context(|| format!("Failed to write the config file at {:?}", path))?;
+    debug!("Updated the config file at {:?}", path);
+
+    Ok(())
+}
+
+fn insert_value(document: &mut DocumentMut, key: &str, value: toml_edit::Value) -> Result<()> {
+    let parts = key.split('.').collect::<Vec<_>>();
+    if parts.iter().any(|part| part.is_empty()) {
+        return Err(anyhow!("Invalid config key '{}'", key));
+    }
+
+    let mut table = document.as_table_mut() as &mut dyn TableLike;
+    for part in &parts[..parts.len() - 1] {
+        // Missing sections become regular [section] tables rather than inline ones
+        let item = table.entry(part).or_insert(Item::Table(Table::new()));
+        table = item.as_table_like_mut()
+            .ok_or_else(|| anyhow!("Cannot set '{}', '{}' is not a table", key, part))?;
+    }
+    table.insert(parts[parts.len() - 1], Item::Value(value));
+
+    Ok(())
+}
+
+// Unknown keys are dropped by the round trip through Config, which is how typos get caught
+fn validate_document(document: &DocumentMut, key: &str) -> Result<()> {
+    let config = toml::from_str::<Config>(&document.to_string())
+        .with_context(|| format!("Invalid value for '{}'", key))?;
+    let known = toml::Value::try_from(&config)?;
+    if key.split('.').try_fold(&known, |value, part| value.get(part)).is_none() {
+        return Err(anyhow!("Unknown config key '{}'", key));
+    }
+
+    Ok(())
+}
+
+fn to_camel_case(key: &str) -> String {
+    key.split('.')
+        .map(|part| {
+            let mut words = part.split('_');
+            let first = words.next().unwrap_or_default().to_string();
+            words.fold(first, |mut camel, word| {
+                let mut chars = word.chars();
+                if let Some(initial) = chars.next() {
+                    camel.extend(initial.to_uppercase());
+                    camel.push_str(chars.as_str());
+                }
+                camel
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn flatten(prefix: &str, value: &toml::Value, entries: &mut Vec<(String, String)>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(&key, value, entries);
+            }
+        }
+        value => entries.push((prefix.to_string(), value.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_camel_case_keys_at_every_level() {
+        let mut document = DocumentMut::new();
+        for (key, value) in [("cacheTtlSecs", "60"), ("presetPaths", "[\"team\"]"), ("git.commitMessage", "init"), ("hooks.postGenerate", "[\"true\"]"), ("defaults.groupId", "com.mycorp")] {
+            document = with_value(&document, key, value).unwrap();
+        }
+
+        let config = toml::from_str::<Config>(&document.to_string()).unwrap();
+        assert_eq!(config.cache_ttl_secs, Some(60));
+        assert_eq!(config.git.commit_message.as_deref(), Some("init"));
+        assert_eq!(config.defaults.group_id.as_deref(), Some("com.mycorp"));
+    }
+
+    #[test]
+    fn rejects_unknown_and_snake_case_keys() {
+        let document = DocumentMut::new();
+        assert!(with_value(&document, "defaults.group_id", "com.mycorp").is_err());
+        assert!(with_value(&document, "git.commit_message", "init").is_err());
+        assert_eq!(with_value(&document, "colour", "1").unwrap_err().to_string(), "Unknown config key 'colour'");
+
+        assert_eq!(to_camel_case("defaults.group_id"), "defaults.groupId");
+        assert_eq!(to_camel_case("cache_ttl_secs"), "cacheTtlSecs");
+        assert_eq!(to_camel_case("git.init"), "git.init");
+    }
+
+    #[test]
+    fn still_loads_snake_case_keys_of_older_files() {
+        let config = toml::from_str::<Config>("cache_ttl_secs = 60\npreset_paths = [\"team\"]\n[git]\ncommit_message = \"init\"\n[hooks]\npost_generate = [\"true\"]\n").unwrap();
+
+        assert_eq!(config.cache_ttl_secs, Some(60));
+        assert_eq!(config.preset_paths, [PathBuf::from("team")]);
+        assert_eq!(config.git.commit_message.as_deref(), Some("init"));
+        assert_eq!(config.hooks.post_generate, ["true"]);
+    }
+}
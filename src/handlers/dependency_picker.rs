@@ -0,0 +1,134 @@
+use std::fmt;
+use inquire::{InquireError, MultiSelect, Select};
+use tracing::debug;
+use crate::{
+    cli::interactive_ui::{base_config, DependencyOption},
+    models::{spring::{DependencyCategories, Value}, version::Version},
+};
+
+const PAGE_SIZE: usize = 12;
+
+enum MenuChoice<'a> {
+    Done(Vec<&'a str>),
+    Search,
+    Category { index: usize, name: &'a str, available: usize, selected: usize },
+}
+
+impl fmt::Display for MenuChoice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MenuChoice::Done(names) if names.is_empty() => write!(f, "✔ Done, no dependencies"),
+            MenuChoice::Done(names) => write!(f, "✔ Done: {}", names.join(", ")),
+            MenuChoice::Search => write!(f, "🔍 Search all categories"),
+            MenuChoice::Category { name, available, selected: 0, .. } => write!(f, "{} ({})", name, available),
+            MenuChoice::Category { name, available, selected, .. } => write!(f, "{} ({}, {} selected)", name, available, selected),
+        }
+    }
+}
+
+/// Picks dependencies the way the web UI groups them: a menu of categories that shows the
+/// selection so far, a MultiSelect per category and a search over every category.
+/// Dependencies that do not support `boot_version` are left out. `None` means Esc on the menu.
+pub fn pick_dependencies(categories: &[DependencyCategories], boot_version: &str, initial: Option<&str>) -> Result<Option<String>, InquireError> {
+    let groups = compatible_categories(categories, boot_version);
+    let available = groups.iter().flat_map(|(_, values)| values.iter().copied()).collect::<Vec<_>>();
+    let hidden = categories.iter().map(|category| category.values.len()).sum::<usize>() - available.len();
+    debug!("{} dependencies are incompatible with Spring Boot {}", hidden, boot_version);
+
+    let initial_ids = initial.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .collect::<Vec<_>>();
+    let mut selected = initial_ids.iter()
+        .filter_map(|id| available.iter().copied().find(|dep| dep.id.eq_ignore_ascii_case(id)))
+        .collect::<Vec<_>>();
+
+    let mut help_message = "Enter to open a category • Type to jump to one • Esc to go back".to_string();
+    if hidden > 0 {
+        help_message.push_str(&format!(" • {} hidden as incompatible with Spring Boot {}", hidden, boot_version));
+    }
+
+    let mut cursor = 0;
+    loop {
+        let mut choices = vec![
+            MenuChoice::Done(selected.iter().map(|dep| dep.name.as_str()).collect()),
+            MenuChoice::Search,
+        ];
+        choices.extend(groups.iter().enumerate().map(|(index, (name, values))| MenuChoice::Category {
+            index,
+            name,
+            available: values.len(),
+            selected: values.iter().filter(|dep| contains(&selected, dep)).count(),
+        }));
+
+        let choice = Select::new("Dependencies:", choices)
+            .with_starting_cursor(cursor)
+            .with_page_size(PAGE_SIZE)
+            .with_help_message(&help_message)
+            .with_render_config(base_config("🧩"))
+            .prompt_skippable()?;
+
+        match choice {
+            None => return Ok(None),
+            Some(MenuChoice::Done(_)) => {
+                let ids = selected.iter().map(|dep| dep.id.as_str()).collect::<Vec<_>>().join(",");
+                debug!("Picked dependencies: {}", ids);
+                return Ok(Some(ids));
+            }
+            Some(MenuChoice::Search) => {
+                cursor = 1;
+                if let Some(chosen) = multi_select("Search dependencies:", &available, &selected)? {
+                    selected = chosen;
+                }
+            }
+            Some(MenuChoice::Category { index, name, .. }) => {
+                cursor = index + 2;
+                let values = &groups[index].1;
+                if let Some(chosen) = multi_select(&format!("{}:", name), values, &selected)? {
+                    selected.retain(|dep| !contains(values, dep));
+                    selected.extend(chosen);
+                }
+            }
+        }
+    }
+}
+
+/// `None` when the user pressed Esc, which keeps the selection as it was.
+fn multi_select<'a>(prompt: &str, values: &[&'a Value], selected: &[&Value]) -> Result<Option<Vec<&'a Value>>, InquireError> {
+    let options = values.iter().copied().map(DependencyOption).collect::<Vec<_>>();
+    let preselected = values.iter()
+        .enumerate()
+        .filter(|(_, dep)| contains(selected, dep))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+
+    let chosen = MultiSelect::new(prompt, options)
+        .with_default(&preselected)
+        .with_page_size(PAGE_SIZE)
+        .with_keep_filter(true)
+        .with_help_message("🔍 Type to search • Space to select • ↑↓ to navigate • Enter to confirm • Esc to cancel")
+        .with_render_config(base_config("🧩"))
+        .prompt_skippable()?;
+
+    Ok(chosen.map(|chosen| chosen.into_iter().map(|option| option.0).collect()))
+}
+
+/// Every category with the dependencies that support `boot_version`, leaving out the empty ones.
+fn compatible_categories<'a>(categories: &'a [DependencyCategories], boot_version: &str) -> Vec<(&'a str, Vec<&'a Value>)> {
+    let version = boot_version.parse::<Version>().ok();
+
+    categories.iter()
+        .map(|category| {
+            let values = category.values.iter()
+                .filter(|dep| version.as_ref().is_none_or(|version| dep.is_compatible_with(version)))
+                .collect::<Vec<_>>();
+            (category.name.as_str(), values)
+        })
+        .filter(|(_, values)| !values.is_empty())
+        .collect()
+}
+
+fn contains(values: &[&Value], dep: &Value) -> bool {
+    values.iter().any(|value| value.id == dep.id)
+}
@@ -0,0 +1,188 @@
+use std::{io::{BufWriter, Write}, path::{Path, PathBuf}};
+use anyhow::Result;
+use serde::Serialize;
+use crate::{
+    cli::root::OutputFormat,
+    client::archive::{is_wrapper_script, ArchiveEntry, ProjectArchive},
+    handlers::list::csv_field,
+};
+
+const BUILD_FILES: [&str; 3] = ["pom.xml", "build.gradle", "build.gradle.kts"];
+const SOURCE_EXTENSIONS: [&str; 3] = ["java", "kt", "groovy"];
+
+#[derive(Serialize, Debug)]
+pub struct Manifest {
+    pub out_dir: PathBuf,
+    pub files: Vec<ManifestFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build_file: Option<KeyFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub main_class: Option<KeyFile>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ManifestFile {
+    pub path: String,
+    pub size: u64,
+    pub executable: bool,
+    pub exists: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct KeyFile {
+    pub path: String,
+    pub contents: String,
+}
+
+impl Manifest {
+    pub fn new(archive: &ProjectArchive, out_dir: &Path) -> Self {
+        let existing = archive.existing_files(out_dir);
+        let mut files = archive.entries.iter()
+            .filter(|entry| !entry.is_dir)
+            .map(|entry| ManifestFile {
+                path: entry_path(entry),
+                size: entry.contents.len() as u64,
+                // Wrapper scripts are marked executable on extraction whatever their mode
+                executable: entry.unix_mode.is_some_and(|mode| mode & 0o111 != 0) || is_wrapper_script(&entry.path),
+                exists: existing.contains(&entry.path),
+            })
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Manifest {
+            out_dir: out_dir.to_path_buf(),
+            files,
+            build_file: key_file(archive, is_build_file),
+            main_class: key_file(archive, is_main_class),
+        }
+    }
+}
+
+pub fn print_dry_run<W: Write>(buf: &mut BufWriter<W>, manifest: &Manifest, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Table => print_tree(buf, manifest)?,
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *buf, manifest)?;
+            writeln!(buf)?;
+        }
+        OutputFormat::Yaml => serde_norway::to_writer(&mut *buf, manifest)?,
+        OutputFormat::Csv => {
+            writeln!(buf, "path,size,executable,exists")?;
+            for file in &manifest.files {
+                writeln!(buf, "{},{},{},{}", csv_field(&file.path), file.size, file.executable, file.exists)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_tree<W: Write>(buf: &mut BufWriter<W>, manifest: &Manifest) -> Result<()> {
+    writeln!(buf, "Dry run, nothing was written. The project would be extracted to {:?}:", manifest.out_dir)?;
+    writeln!(buf)?;
+
+    let mut printed_dirs: Vec<String> = Vec::new();
+    for file in &manifest.files {
+        let parts = file.path.split('/').collect::<Vec<_>>();
+        for depth in 0..parts.len() - 1 {
+            let dir = parts[..=depth].join("/");
+            if !printed_dirs.contains(&dir) {
+                writeln!(buf, "{}{}/", "  ".repeat(depth), parts[depth])?;
+                printed_dirs.push(dir);
+            }
+        }
+
+        let mut notes = vec![format_size(file.size)];
+        if file.executable {
+            notes.push("executable".to_string());
+        }
+        if file.exists {
+            notes.push("exists".to_string());
+        }
+        writeln!(buf, "{}{}  ({})", "  ".repeat(parts.len() - 1), parts[parts.len() - 1], notes.join(", "))?;
+    }
+
+    let existing = manifest.files.iter().filter(|file| file.exists).count();
+    if existing > 0 {
+        writeln!(buf)?;
+        writeln!(buf, "{} of these files already exist in {:?}", existing, manifest.out_dir)?;
+    }
+
+    for key_file in [&manifest.build_file, &manifest.main_class].into_iter().flatten() {
+        writeln!(buf)?;
+        writeln!(buf, "--- {} ---", key_file.path)?;
+        write!(buf, "{}", key_file.contents)?;
+        if !key_file.contents.ends_with('\n') {
+            writeln!(buf)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn key_file(archive: &ProjectArchive, matches: fn(&Path) -> bool) -> Option<KeyFile> {
+    archive.entries.iter()
+        .filter(|entry| !entry.is_dir && matches(&entry.path))
+        .min_by_key(|entry| entry.path.components().count())
+        .map(|entry| KeyFile {
+            path: entry_path(entry),
+            contents: String::from_utf8_lossy(&entry.contents).into_owned(),
+        })
+}
+
+fn is_build_file(path: &Path) -> bool {
+    path.components().count() <= 2
+        && path.file_name().and_then(|name| name.to_str()).is_some_and(|name| BUILD_FILES.contains(&name))
+}
+
+fn is_main_class(path: &Path) -> bool {
+    let in_main_sources = path.to_string_lossy().replace('\\', "/").contains("src/main/");
+    let is_source = path.extension().and_then(|extension| extension.to_str()).is_some_and(|extension| SOURCE_EXTENSIONS.contains(&extension));
+    let is_application = path.file_stem().and_then(|stem| stem.to_str()).is_some_and(|stem| stem.ends_with("Application"));
+
+    in_main_sources && is_source && is_application
+}
+
+// Archive paths are always shown with '/' regardless of the platform
+fn entry_path(entry: &ArchiveEntry) -> String {
+    entry.path.iter()
+        .map(|part| part.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn format_size(size: u64) -> String {
+    match size {
+        0..1024 => format!("{} B", size),
+        1024..1_048_576 => format!("{:.1} KiB", size as f64 / 1024.0),
+        _ => format!("{:.1} MiB", size as f64 / 1_048_576.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, unix_mode: Option<u32>) -> ArchiveEntry {
+        ArchiveEntry { path: PathBuf::from(path), is_dir: false, unix_mode, contents: Vec::new() }
+    }
+
+    #[test]
+    fn wrapper_scripts_are_reported_as_executable_whatever_their_mode() {
+        let archive = ProjectArchive {
+            entries: vec![
+                file("demo/mvnw", Some(0o644)),
+                file("demo/gradlew", None),
+                file("demo/run.sh", Some(0o755)),
+                file("demo/pom.xml", Some(0o644)),
+            ],
+        };
+        let manifest = Manifest::new(&archive, Path::new("/nonexistent"));
+
+        let executables = manifest.files.iter()
+            .filter(|file| file.executable)
+            .map(|file| file.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(executables, ["demo/gradlew", "demo/mvnw", "demo/run.sh"]);
+    }
+}
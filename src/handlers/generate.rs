@@ -0,0 +1,62 @@
+use std::{io::{stdout, BufWriter}, path::PathBuf};
+use anyhow::{Context, Result};
+use tracing::debug;
+use crate::{
+    cli::root::OutputFormat,
+    client::{archive::{OutputMode, OverwritePolicy}, spring_initializr::SpringInitializr},
+    handlers::{answers::save_answers, dry_run::{print_dry_run, Manifest}, git::{init_repository, GitOptions}, hooks::run_post_generate_hooks},
+    models::spring::QueryParam,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    pub out_dir: PathBuf,
+    pub policy: OverwritePolicy,
+    pub mode: OutputMode,
+    pub save_answers_path: Option<PathBuf>,
+    pub dry_run: bool,
+    pub format: OutputFormat,
+    pub git: Option<GitOptions>,
+    pub post_generate_hooks: Vec<String>,
+}
+
+pub async fn generate(initializr: &SpringInitializr, query_params: QueryParam, options: &GenerateOptions) -> Result<()> {
+    if options.dry_run {
+        let archive = initializr.download_project(&query_params, options.mode).await
+            .with_context(|| "Failed to download the spring boot project")?;
+        let manifest = Manifest::new(&archive, &options.out_dir);
+        debug!("Dry run manifest: {:?}", manifest);
+
+        let mut buf = BufWriter::new(stdout().lock());
+        return print_dry_run(&mut buf, &manifest, options.format)
+            .with_context(|| "Failed to print the dry run");
+    }
+
+    let project_dir = initializr.generate_project(&query_params, options.out_dir.clone(), options.policy, options.mode).await
+        .with_context(|| "Failed to generate a spring boot project")?;
+    // Saved only now, answers of a failed generation are not worth replaying
+    if let Some(path) = options.save_answers_path.as_deref() {
+        save_answers(path, &query_params)?;
+    }
+
+    // Hooks run first, so that the files they add end up in the initial commit
+    if !options.post_generate_hooks.is_empty() {
+        if options.mode == OutputMode::BuildFile {
+            println!("Skipping the postGenerate hooks, only the build file was generated");
+        } else {
+            run_post_generate_hooks(&options.post_generate_hooks, &project_dir, &query_params)
+                .with_context(|| format!("The project was generated in {:?}, but a postGenerate hook failed", project_dir))?;
+        }
+    }
+
+    if let Some(git) = options.git.as_ref() {
+        if options.mode == OutputMode::BuildFile {
+            println!("Skipping git init, only the build file was generated");
+        } else {
+            init_repository(&project_dir, git)
+                .with_context(|| format!("The project was generated, but initializing a git repository in {:?} failed", project_dir))?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use std::{fs, io::ErrorKind, path::Path, process::Command};
+use anyhow::{anyhow, Context, Result};
+use tracing::{debug, info, warn};
+
+pub const DEFAULT_COMMIT_MESSAGE: &str = "Initial commit from xspring";
+
+#[derive(Debug, Clone)]
+pub struct GitOptions {
+    pub remote: Option<String>,
+    pub commit_message: String,
+    pub ignore: Vec<String>,
+}
+
+/// Runs `git init`, commits the generated project and adds `origin` if a remote is given.
+/// A missing git binary only prints a warning, the project has been generated at this point.
+pub fn init_repository(project_dir: &Path, options: &GitOptions) -> Result<()> {
+    match Command::new("git").arg("--version").output() {
+        Ok(output) => debug!("Using {}", String::from_utf8_lossy(&output.stdout).trim()),
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            warn!("git was not found, skipping the repository initialization");
+            eprintln!("git was not found on the PATH, skipping the repository initialization");
+            return Ok(());
+        }
+        Err(err) => return Err(err).with_context(|| "Failed to run git"),
+    }
+
+    if git(project_dir, &["rev-parse", "--is-inside-work-tree"]).is_ok_and(|inside| inside.trim() == "true") {
+        println!("{:?} is already inside a git repository, skipping git init", project_dir);
+        return Ok(());
+    }
+
+    git(project_dir, &["init", "--quiet"])?;
+    if let Some(remote) = options.remote.as_deref() {
+        git(project_dir, &["remote", "add", "origin", remote])?;
+    }
+    // Only touch the generated files once the repository is actually created
+    if !options.ignore.is_empty() {
+        add_ignore_entries(project_dir, &options.ignore)?;
+    }
+    git(project_dir, &["add", "--all"])?;
+    git(project_dir, &["commit", "--quiet", "--message", &options.commit_message])?;
+    info!("Initialized a git repository in {:?}", project_dir);
+    println!("Initialized a git repository with an initial commit in {:?}", project_dir);
+    if let Some(remote) = options.remote.as_deref() {
+        println!("Added the remote origin {}", remote);
+    }
+
+    Ok(())
+}
+
+fn add_ignore_entries(project_dir: &Path, entries: &[String]) -> Result<()> {
+    let path = project_dir.join(".gitignore");
+    if path.symlink_metadata().is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+        return Err(anyhow!("Refusing to add the ignore entries to {:?}, it is a symlink", path));
+    }
+    let existing = match fs::read_to_string(&path) {
+        Ok(existing) => existing,
+        Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {:?}", path)),
+    };
+
+    let merged = merge_gitignore(&existing, &entries.join("\n"));
+    if merged != existing {
+        fs::write(&path, merged)
+            .with_context(|| format!("Failed to write {:?}", path))?;
+        debug!("Added the configured ignore entries to {:?}", path);
+    }
+
+    Ok(())
+}
+
+/// Appends the lines of `addition` that `existing` does not contain yet, keeping the order of both.
+fn merge_gitignore(existing: &str, addition: &str) -> String {
+    let present = existing.lines().map(str::trim).collect::<Vec<_>>();
+    let missing = addition.lines()
+        .filter(|line| !line.trim().is_empty() && !present.contains(&line.trim()))
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return existing.to_string();
+    }
+
+    let mut merged = existing.to_string();
+    if !merged.is_empty() && !merged.ends_with('\n') {
+        merged.push('\n');
+    }
+    for line in missing {
+        merged.push_str(line);
+        merged.push('\n');
+    }
+
+    merged
+}
+
+fn git(project_dir: &Path, args: &[&str]) -> Result<String> {
+    debug!("Running git {:?} in {:?}", args, project_dir);
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(project_dir)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        return Err(anyhow!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_gitignore_appends_missing_lines() {
+        assert_eq!(merge_gitignore("target/\n.idea", "target/\n\nbuild/\n"), "target/\n.idea\nbuild/\n");
+        assert_eq!(merge_gitignore("target/\n", " target/ "), "target/\n");
+        assert_eq!(merge_gitignore("", ".env\n*.local"), ".env\n*.local\n");
+    }
+}
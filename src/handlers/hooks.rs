@@ -0,0 +1,75 @@
+use std::{path::Path, process::Command};
+use anyhow::{anyhow, Context, Result};
+use tracing::{debug, error, info};
+use crate::models::spring::QueryParam;
+
+/// Runs the configured `postGenerate` commands one after the other in `project_dir`,
+/// stopping at the first one that fails.
+pub fn run_post_generate_hooks(hooks: &[String], project_dir: &Path, query_params: &QueryParam) -> Result<()> {
+    let env = hook_env(project_dir, query_params)?;
+
+    for hook in hooks {
+        println!("Running hook: {}", hook);
+        info!("Running postGenerate hook '{}' in {:?}", hook, project_dir);
+
+        let status = shell(hook)
+            .current_dir(project_dir)
+            .envs(env.iter().map(|(key, value)| (key, value)))
+            .status()
+            .with_context(|| format!("Failed to start the postGenerate hook '{}'", hook))?;
+
+        if !status.success() {
+            error!("postGenerate hook '{}' failed with {}", hook, status);
+            return Err(anyhow!("The postGenerate hook '{}' failed with {}", hook, status));
+        }
+        debug!("postGenerate hook '{}' finished", hook);
+    }
+
+    Ok(())
+}
+
+/// Every query parameter as `XSPRING_<PARAM>`, e.g. `XSPRING_BOOT_VERSION`, plus `XSPRING_PROJECT_DIR`.
+fn hook_env(project_dir: &Path, query_params: &QueryParam) -> Result<Vec<(String, String)>> {
+    let serde_json::Value::Object(params) = serde_json::to_value(query_params)? else {
+        return Err(anyhow!("Query params did not serialize to an object"));
+    };
+
+    let mut env = params.into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::String(value) => value,
+                value => value.to_string(),
+            };
+            (format!("XSPRING_{}", screaming_snake_case(&key)), value)
+        })
+        .collect::<Vec<_>>();
+    env.push(("XSPRING_PROJECT_DIR".to_string(), project_dir.display().to_string()));
+
+    Ok(env)
+}
+
+fn screaming_snake_case(key: &str) -> String {
+    let mut converted = String::with_capacity(key.len() + 4);
+    for character in key.chars() {
+        if character.is_uppercase() && !converted.is_empty() {
+            converted.push('_');
+        }
+        converted.push(character.to_ascii_uppercase());
+    }
+
+    converted
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
@@ -1,261 +1,507 @@
-use inquire::{required, validator::Validation, MultiSelect, Select, Text, };
-use anyhow::{Context, Result};
-use tracing::{debug, trace};
-use crate::{cli::interactive_ui::base_config, client::spring_initializr::get_metadata, models::spring::QueryParam};
+use std::{borrow::Cow, fmt, io::{stdin, IsTerminal}};
+use inquire::{validator::Validation, InquireError, Select, Text, };
+use anyhow::{anyhow, Context, Result};
+use tracing::{debug, info, trace};
+use crate::{
+    cli::interactive_ui::base_config,
+    client::spring_initializr::SpringInitializr,
+    handlers::{
+        dependency_picker::pick_dependencies,
+        resolve::{resolve_boot_version, resolve_query_params, resolve_value},
+        validation::{derive_package_name, validate_artifact_id, validate_group_id, validate_package_name},
+    },
+    models::{answers::Answers, spring::{normalize_boot_version, DependencyCategories, InitializrMetadata, MetadataValue, QueryParam, Value}, version::Version},
+};
 
-pub async fn pure_interactivity() -> Result<QueryParam> {
-    let spring_metadata = get_metadata().await?;
+/// Prompts for every value missing from `answers`, prefilling the prompts from the configured `defaults`.
+pub async fn pure_interactivity(initializr: &SpringInitializr, answers: Answers, defaults: &Answers) -> Result<QueryParam> {
+    let spring_metadata = initializr.get_metadata().await?;
     trace!("Spring Metadata: {:?}", spring_metadata);
 
-    let group_id = Text::new("Group ID:")
-        .with_help_message("e.g. com.example")
-        .with_validator(required!())
-        .with_validator(|input: &str| {
-            if input.contains(' ') {
-                Ok(Validation::Invalid("This field's input cannot contain spaces".into()))
-            }else {
-                Ok(Validation::Valid)
+    let mut draft = resolve_given(&spring_metadata, answers)?;
+    let steps = Field::ALL.into_iter()
+        .filter(|field| field.answer(&draft).is_none())
+        .collect::<Vec<_>>();
+    run_wizard(&spring_metadata, &steps, &mut draft, defaults)?;
+
+    let query_params = resolve_query_params(&spring_metadata, draft)?;
+    debug!("Interactive query params: {:?}", query_params);
+
+    review(initializr, query_params).await
+}
+
+pub async fn quick_interactivity(initializr: &SpringInitializr, answers: Answers, defaults: &Answers, maven: bool, extended: bool, deps: bool, with_review: bool) -> Result<QueryParam>{
+    let spring_metadata = initializr.get_metadata().await
+        .with_context(|| "Failed to get the metadata")?;
+
+    let mut draft = resolve_given(&spring_metadata, answers)?;
+    if maven {
+        draft.project_type = Some("maven-project".to_string());
+    }
+
+    let mut steps = vec![Field::GroupId, Field::ArtifactId];
+    if !extended {
+        steps.extend([Field::Name, Field::Description]);
+    }
+    if deps {
+        steps.push(Field::Dependencies);
+    }
+    steps.retain(|field| field.answer(&draft).is_none());
+    run_wizard(&spring_metadata, &steps, &mut draft, defaults)?;
+
+    // Fields quick mode does not prompt for come from the answers, then the config defaults
+    let query_params = resolve_query_params(&spring_metadata, draft.or(defaults.clone()))?;
+    debug!("Quick query params: {:?}", query_params);
+
+    if with_review {
+        return review(initializr, query_params).await;
+    }
+
+    Ok(query_params)
+}
+
+/// One question of the interactive flows. The pure and quick flows ask a different
+/// subset of these, the review and the TUI edit any of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    GroupId,
+    ArtifactId,
+    Name,
+    Description,
+    PackageName,
+    ProjectType,
+    Language,
+    BootVersion,
+    Packaging,
+    JavaVersion,
+    Dependencies,
+}
+
+impl Field {
+    pub const ALL: [Field; 11] = [
+        Field::GroupId, Field::ArtifactId, Field::Name, Field::Description, Field::PackageName, Field::ProjectType,
+        Field::Language, Field::BootVersion, Field::Packaging, Field::JavaVersion, Field::Dependencies,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Field::GroupId => "Group ID",
+            Field::ArtifactId => "Artifact ID",
+            Field::Name => "Display Name",
+            Field::Description => "Description",
+            Field::PackageName => "Package Name",
+            Field::ProjectType => "Project Type",
+            Field::Language => "Language",
+            Field::BootVersion => "Spring Boot Version",
+            Field::Packaging => "Package Type",
+            Field::JavaVersion => "Java Version",
+            Field::Dependencies => "Dependencies",
+        }
+    }
+
+    fn emoji(self) -> &'static str {
+        match self {
+            Field::GroupId => "📦",
+            Field::ArtifactId => "🎫",
+            Field::Name => "📝",
+            Field::Description => "💡",
+            Field::PackageName => "📂",
+            Field::ProjectType => "🧰",
+            Field::Language => "💻",
+            Field::BootVersion => "🚀",
+            Field::Packaging => "🎁",
+            Field::JavaVersion => "☕",
+            Field::Dependencies => "🧩",
+        }
+    }
+
+    fn help(self) -> &'static str {
+        match self {
+            Field::GroupId => "e.g. com.example",
+            Field::ArtifactId => "e.g. my-awesome-project",
+            Field::Name => "This will be the display name for your project",
+            Field::Description => "A brief description of your project.",
+            Field::PackageName => "The base package of your sources, derived from the Group ID and Artifact ID",
+            Field::ProjectType => "Choose the build system for your project (Maven or Gradle)",
+            Field::Language => "Choose the programming language for your project",
+            Field::BootVersion => "Choose the version of Spring Boot for your project",
+            Field::Packaging => "Choose how your project will be packaged",
+            Field::JavaVersion => "Select java version for your project",
+            Field::Dependencies => "Pick dependencies by category or search all of them",
+        }
+    }
+
+    fn answer(self, answers: &Answers) -> Option<&str> {
+        match self {
+            Field::GroupId => answers.group_id.as_deref(),
+            Field::ArtifactId => answers.artifact_id.as_deref(),
+            Field::Name => answers.name.as_deref(),
+            Field::Description => answers.description.as_deref(),
+            Field::PackageName => answers.package_name.as_deref(),
+            Field::ProjectType => answers.project_type.as_deref(),
+            Field::Language => answers.language.as_deref(),
+            Field::BootVersion => answers.boot_version.as_deref(),
+            Field::Packaging => answers.packaging.as_deref(),
+            Field::JavaVersion => answers.java_version.as_deref(),
+            Field::Dependencies => answers.dependencies.as_deref(),
+        }
+    }
+
+    fn set_answer(self, answers: &mut Answers, value: String) {
+        let slot = match self {
+            Field::GroupId => &mut answers.group_id,
+            Field::ArtifactId => &mut answers.artifact_id,
+            Field::Name => &mut answers.name,
+            Field::Description => &mut answers.description,
+            Field::PackageName => &mut answers.package_name,
+            Field::ProjectType => &mut answers.project_type,
+            Field::Language => &mut answers.language,
+            Field::BootVersion => &mut answers.boot_version,
+            Field::Packaging => &mut answers.packaging,
+            Field::JavaVersion => &mut answers.java_version,
+            Field::Dependencies => &mut answers.dependencies,
+        };
+        *slot = Some(value);
+    }
+
+    /// The package name shows the derived suggestion while none was chosen.
+    pub fn value(self, params: &QueryParam) -> Cow<'_, str> {
+        match self {
+            Field::GroupId => Cow::Borrowed(&params.group_id),
+            Field::ArtifactId => Cow::Borrowed(&params.artifact_id),
+            Field::Name => Cow::Borrowed(&params.name),
+            Field::Description => Cow::Borrowed(&params.description),
+            Field::PackageName => match &params.package_name {
+                Some(package_name) => Cow::Borrowed(package_name),
+                None => Cow::Owned(derive_package_name(&params.group_id, &params.artifact_id)),
+            },
+            Field::ProjectType => Cow::Borrowed(&params.project_type),
+            Field::Language => Cow::Borrowed(&params.language),
+            Field::BootVersion => Cow::Borrowed(&params.boot_version),
+            Field::Packaging => Cow::Borrowed(&params.packaging),
+            Field::JavaVersion => Cow::Borrowed(&params.java_version),
+            Field::Dependencies => Cow::Borrowed(&params.dependencies),
+        }
+    }
+}
+
+/// Asks the `steps` in order. Esc goes back to the previous step, which starts from the
+/// earlier answer, and Esc on the first step aborts.
+fn run_wizard(spring_metadata: &InitializrMetadata, steps: &[Field], draft: &mut Answers, defaults: &Answers) -> Result<()> {
+    let mut index = 0;
+    while let Some(field) = steps.get(index).copied() {
+        let initial = field.answer(draft).or(field.answer(defaults)).map(str::to_string).or_else(|| {
+            (field == Field::PackageName).then(|| derived_package_name(draft))
+        });
+        let boot_version = draft.boot_version.as_deref()
+            .or(defaults.boot_version.as_deref())
+            .map_or_else(|| normalize_boot_version(&spring_metadata.boot_version.default), normalize_boot_version);
+
+        match prompt_step(spring_metadata, field, initial.as_deref(), &boot_version)? {
+            Some(answer) => {
+                debug!("{}: {:?}", field.label(), answer);
+                // The suggested package is left to the Initializr, so it follows the ids when going back
+                let derived = derived_package_name(draft);
+                field.set_answer(draft, answer);
+                if draft.package_name.as_deref() == Some(derived.as_str()) {
+                    draft.package_name = None;
+                }
+                index += 1;
             }
-        })
-        .with_render_config(base_config("📦"))
-        .prompt()
-        .with_context(|| "Failed to get input for Group ID")?;
-    debug!("Group ID: {}", group_id);
-
-    let artifact_id = Text::new("Artifact ID:")
-        .with_help_message("e.g. my-awesome-project")
-        .with_validator(required!())
-        .with_validator(|input: &str| {
-            if input.contains(' ') {
-                Ok(Validation::Invalid("This field's input cannot contain spaces".into()))
-            }else {
-                Ok(Validation::Valid)
+            None if index == 0 => {
+                info!("Generation aborted at the first prompt");
+                return Err(anyhow!("Aborted, no project was generated"));
             }
-        })
-        .with_render_config(base_config("🎫"))
-        .prompt()
-        .with_context(|| "Failed to get input for Artifact ID")?;
-    debug!("Artifact ID: {}", artifact_id);
-
-    let mut name = Text::new("Display Name:")
-        .with_help_message("This will be the display name for your project")
-        .with_placeholder(&spring_metadata.name.default)
-        .with_render_config(base_config("📝"))
-        .prompt()
-        .with_context(|| "Failed to get input for Project Name")?;
-    debug!("Project Name Choice: {}", name);
-    if name.is_empty() {
-        name = spring_metadata.name.default;
-        debug!("Project Name after replacing the empty string: {}", name);
+            None => {
+                debug!("Going back from {} to {}", field.label(), steps[index - 1].label());
+                index -= 1;
+            }
+        }
     }
 
-    let mut description = Text::new("Project Description:")
-        .with_help_message("A brief description of your project.")
-        .with_placeholder(&spring_metadata.description.default)
-        .with_render_config(base_config("💡"))
-        .prompt()
-        .with_context(|| "Failed to get input for Description")?;
-    debug!("Project Description Choice: {}", description);
-    if description.is_empty() {
-        description = spring_metadata.description.default;
-        debug!("Project description after replacing the empty string: {}", description);
+    Ok(())
+}
+
+fn derived_package_name(draft: &Answers) -> String {
+    derive_package_name(draft.group_id.as_deref().unwrap_or_default(), draft.artifact_id.as_deref().unwrap_or_default())
+}
+
+/// Prompts for a single field, starting from `initial`. `None` means the user pressed Esc.
+fn prompt_step(spring_metadata: &InitializrMetadata, field: Field, initial: Option<&str>, boot_version: &str) -> Result<Option<String>> {
+    let prompt = format!("{}:", field.label());
+    let answer = match field {
+        Field::GroupId | Field::ArtifactId | Field::PackageName => {
+            let validate = match field {
+                Field::GroupId => validate_group_id,
+                Field::ArtifactId => validate_artifact_id,
+                _ => validate_package_name,
+            };
+            Text::new(&prompt)
+                .with_help_message(field.help())
+                .with_initial_value(initial.unwrap_or_default())
+                .with_validator(move |input: &str| {
+                    match validate(input) {
+                        Ok(()) => Ok(Validation::Valid),
+                        Err(err) => Ok(Validation::Invalid(err.to_string().into())),
+                    }
+                })
+                .with_render_config(base_config(field.emoji()))
+                .prompt_skippable()
+        }
+        Field::Name | Field::Description => {
+            let default = match field {
+                Field::Name => &spring_metadata.name.default,
+                _ => &spring_metadata.description.default,
+            };
+            Text::new(&prompt)
+                .with_help_message(field.help())
+                .with_placeholder(default)
+                .with_initial_value(initial.unwrap_or_default())
+                .with_render_config(base_config(field.emoji()))
+                .prompt_skippable()
+                .map(|answer| answer.map(|answer| if answer.is_empty() { default.clone() } else { answer }))
+        }
+        Field::ProjectType => select_value(&prompt, field, &spring_metadata.project_type, initial),
+        Field::Language => select_value(&prompt, field, &spring_metadata.language, initial),
+        Field::BootVersion => select_value(&prompt, field, &spring_metadata.boot_version, initial)
+            .map(|answer| answer.map(|boot_version| normalize_boot_version(&boot_version))),
+        Field::Packaging => select_value(&prompt, field, &spring_metadata.packaging, initial),
+        Field::JavaVersion => select_value(&prompt, field, &spring_metadata.java_version, initial),
+        Field::Dependencies => pick_dependencies(&spring_metadata.dependencies.values, boot_version, initial),
+    };
+
+    answer.with_context(|| format!("Failed to get input for {}", field.label()))
+}
+
+fn select_value(prompt: &str, field: Field, metadata: &MetadataValue, initial: Option<&str>) -> Result<Option<String>, InquireError> {
+    let cursor = match initial {
+        Some(_) => preferred_index(&metadata.values, initial),
+        None => metadata.values.iter().position(|value| value.id == metadata.default).unwrap_or(0),
+    };
+    let value = Select::new(prompt, metadata.values.clone())
+        .with_starting_cursor(cursor)
+        .with_help_message(field.help())
+        .with_render_config(base_config(field.emoji()))
+        .prompt_skippable()?;
+
+    Ok(value.map(|value| value.id))
+}
+
+/// Checks the values given up front and swaps them for the ids the Initializr expects, so
+/// a typo fails before the first prompt. Dependencies are resolved at the end, once the
+/// Spring Boot version is known.
+fn resolve_given(spring_metadata: &InitializrMetadata, mut answers: Answers) -> Result<Answers> {
+    if let Some(group_id) = answers.group_id.as_deref() {
+        validate_group_id(group_id)?;
     }
+    if let Some(artifact_id) = answers.artifact_id.as_deref() {
+        validate_artifact_id(artifact_id)?;
+    }
+    if let Some(package_name) = answers.package_name.as_deref() {
+        validate_package_name(package_name)?;
+    }
+
+    answers.project_type = answers.project_type.as_deref()
+        .map(|project_type| resolve_value("project type", Some(project_type), &spring_metadata.project_type))
+        .transpose()?;
+    answers.language = answers.language.as_deref()
+        .map(|language| resolve_value("language", Some(language), &spring_metadata.language))
+        .transpose()?;
+    answers.boot_version = answers.boot_version.as_deref()
+        .map(|boot_version| resolve_boot_version(Some(boot_version), &spring_metadata.boot_version))
+        .transpose()?;
+    answers.packaging = answers.packaging.as_deref()
+        .map(|packaging| resolve_value("packaging", Some(packaging), &spring_metadata.packaging))
+        .transpose()?;
+    answers.java_version = answers.java_version.as_deref()
+        .map(|java_version| resolve_value("Java version", Some(java_version), &spring_metadata.java_version))
+        .transpose()?;
 
-    let project_types = spring_metadata.project_type.values;
-    let project_type = Select::new("Project Type:", project_types)
-        .with_help_message("Choose the build system for your project (Maven or Gradle)")
-        .with_render_config(base_config("🧰"))
-        .prompt()
-        .with_context(|| "Failed to get input for Project Type")?;
-    debug!("Project type: {:?}", project_type);
-
-    let languages = spring_metadata.language.values;
-    let language = Select::new("Language:", languages)
-        .with_help_message("Choose the programming language for your project")
-        .with_render_config(base_config("💻"))
-        .prompt()
-        .with_context(|| "Failed to get input for Language")?;
-    debug!("Lanuage Choice: {:?}", language);
-
-    let mut boot_versions = spring_metadata.boot_version.values;
-    trace!("Spring Boot Versions: {:?}", boot_versions);
-    let default_boot_version = spring_metadata.boot_version.default;
-    let default_boot_version_idx = boot_versions.iter().position(|v| v.id == default_boot_version).unwrap_or(0);
-    let temp = boot_versions[default_boot_version_idx].clone();
-    boot_versions[default_boot_version_idx] = boot_versions[0].clone();
-    boot_versions[0] = temp;
-
-    let boot_version = Select::new("Spring Boot Version:", boot_versions)
-        .with_help_message("Choose the version of Spring Boot for your project")
-        .with_render_config(base_config("🚀"))
-
-        .prompt()
-        .with_context(|| "Failed to get input for Spring Boot Version")?;
-    debug!("Selected Boot Version: {:?}", boot_version);
-    debug!("Select Boot Version Id: {:?}", boot_version.id);
-
-
-    let packages = spring_metadata.packaging.values;
-    let packaging = Select::new("Package Type:", packages)
-        .with_help_message("Choose how your project will be packaged")
-        .with_render_config(base_config("🎁"))
-        .prompt()
-        .with_context(|| "Failed to get input for Package Type")?;
-    debug!("Project Packaging Choice: {:?}", packaging);
-
-    let mut java_versions = spring_metadata.java_version.values;
-    let default_java_version = spring_metadata.java_version.default;
-    let default_java_version_idx = java_versions.iter().position(|v| v.id == default_java_version).unwrap_or(0);
-    java_versions.swap(0, default_java_version_idx);
-    let java_version = Select::new("Java Version", java_versions)
-        .with_help_message("Select java version for your project")
-        .with_render_config(base_config("☕"))
-        .prompt()
-        .with_context(|| "Failed to get input for Java Version")?;
-    debug!("Select Java Version: {:?}", java_version);
-
-    let dependency_names = spring_metadata.dependencies.values.iter()
-        .flat_map(|category| category.values.iter())
-        .collect();
-    trace!("All Dependency Names: {:?}", dependency_names);
-    let dependencies = MultiSelect::new("Dependencies", dependency_names)   
-        .with_page_size(5)
-        .with_keep_filter(true)
-        .with_help_message("🔍 Type to search • Space to select • ↑↓ to navigate • Enter to confirm")
-        .with_render_config(base_config("🧩"))
-        .prompt()
-        .with_context(|| "Failed to get input for Dependencies")?;
-    debug!("Selected Dependencies: {:?}", dependencies);
-    
-    Ok(QueryParam {
-        project_type: project_type.id.clone(),
-        language: language.id.clone(),
-        boot_version: boot_version.id.replace(".RELEASE", "").replace(".BUILD-SNAPSHOT", "-SNAPSHOT").replace(".M", "-M").replace(".RC", "-RC"),
-        group_id ,
-        artifact_id: artifact_id.clone(),
-        name,
-        description,
-        packaging: packaging.id.clone(),
-        java_version: java_version.id.clone(),
-        dependencies: dependencies.iter().map(|dep| dep.id.clone() ).collect::<Vec<_>>().join(","),
-        base_dir: artifact_id
-    })
+    Ok(answers)
+}
+
+enum ReviewChoice {
+    Generate,
+    Edit(Field, String),
+    Abort,
+}
+
+impl fmt::Display for ReviewChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReviewChoice::Generate => write!(f, "Generate the project"),
+            ReviewChoice::Edit(field, value) if value.is_empty() => write!(f, "{:<20} (none)", field.label()),
+            ReviewChoice::Edit(field, value) => write!(f, "{:<20} {}", field.label(), value),
+            ReviewChoice::Abort => write!(f, "Abort"),
+        }
+    }
 }
 
-pub async fn quick_interactivity(maven: bool, extended: bool, deps: bool) -> Result<QueryParam>{
-    let spring_metadata = get_metadata().await
+/// Lists every chosen value and lets the user edit single fields until they generate or abort.
+/// Without a terminal there is nobody to review, so the values are used as they are.
+pub async fn review(initializr: &SpringInitializr, mut query_params: QueryParam) -> Result<QueryParam> {
+    if !stdin().is_terminal() {
+        debug!("Skipping the review, stdin is not a terminal");
+        return Ok(query_params);
+    }
+
+    let spring_metadata = initializr.get_metadata().await
         .with_context(|| "Failed to get the metadata")?;
+    let mut cursor = 0;
+
+    loop {
+        let mut choices = vec![ReviewChoice::Generate];
+        choices.extend(Field::ALL.iter().map(|field| ReviewChoice::Edit(*field, field.value(&query_params).to_string())));
+        choices.push(ReviewChoice::Abort);
+        let page_size = choices.len();
 
-    let group_id: String = Text::new("Group ID:")
-        .with_help_message("e.g. com.example")
-        .with_validator(required!())
-        .with_validator(|input: &str| {
-            if input.contains(' ') {
-                Ok(Validation::Invalid("This field's input cannot contain spaces".into()))
-            }else {
-                Ok(Validation::Valid)
+        let choice = Select::new("Review your project:", choices)
+            .with_starting_cursor(cursor)
+            .with_page_size(page_size)
+            .with_help_message("↑↓ to navigate • Enter on a field to edit it • Esc to abort")
+            .with_render_config(base_config("📋"))
+            .prompt_skippable()
+            .with_context(|| "Failed to get input for the review")?;
+
+        match choice {
+            Some(ReviewChoice::Generate) => {
+                debug!("Reviewed project: {:?}", query_params);
+                return Ok(query_params);
+            }
+            Some(ReviewChoice::Edit(field, _)) => {
+                cursor = Field::ALL.iter().position(|candidate| *candidate == field).unwrap_or(0) + 1;
+                edit_field(&spring_metadata, &mut query_params, field)?;
             }
-        })
-        .with_render_config(base_config("📦"))
-        .prompt()
-        .with_context(|| "Failed to get input for Group ID")?;
-    debug!("Group ID: {}", group_id);
-
-    let artifact_id = Text::new("Artifact ID:")
-        .with_help_message("e.g. my-awesome-project")
-        .with_validator(required!())
-        .with_validator(|input: &str| {
-            if input.contains(' ') {
-                Ok(Validation::Invalid("This field's input cannot contain spaces".into()))
-            }else {
-                Ok(Validation::Valid)
+            Some(ReviewChoice::Abort) | None => {
+                info!("Generation aborted in the review");
+                return Err(anyhow!("Aborted, no project was generated"));
             }
-        })
-        .with_render_config(base_config("🎫"))
-        .prompt()
-        .with_context(|| "Failed to get input for Artifact ID")?;
-    debug!("Artifact ID: {}", artifact_id);
-        
-    let mut name: Option<String> = None;
-    let mut description: Option<String> = None;
-    let mut dependencies: Option<String> = None;
-    let mut project_type = spring_metadata.project_type.default;
+        }
+    }
+}
 
-    if !extended {
-        let _name = Text::new("Display Name:")
-            .with_help_message("This will be the display name for your project")
-            .with_validator(required!())
-            .with_validator(|input: &str| {
-                if input.contains(' ') {
-                    Ok(Validation::Invalid("This field's input cannot contain spaces".into()))
-                }else {
-                    Ok(Validation::Valid)
-                }
-            })
-            .with_render_config(base_config("📝"))
-            .prompt()
-            .with_context(|| "Failed to get input for Project Name")?;
-        name = Some(_name);
-        debug!("Project Name Choice: {:?}", name);
-
-        let _description = Text::new("Description:")
-            .with_help_message("A brief description of your project.")
-            .with_validator(required!())
-            .with_validator(|input: &str| {
-                if input.contains(' ') {
-                    Ok(Validation::Invalid("This field's input cannot contain spaces".into()))
-                }else {
-                    Ok(Validation::Valid)
-                }
-            })
-            .with_render_config(base_config("💡"))
-            .prompt()
-            .with_context(|| "Failed to get input for Description")?;
-        description = Some(_description);
-        debug!("Project Description Choice: {:?}", description);
-      
+/// Asks the step of a single field again, starting from its current value. Esc keeps the value.
+fn edit_field(spring_metadata: &InitializrMetadata, query_params: &mut QueryParam, field: Field) -> Result<()> {
+    let Some(edited) = prompt_step(spring_metadata, field, Some(&field.value(query_params)), &query_params.boot_version)? else {
+        return Ok(());
+    };
+    debug!("Edited {}: {:?}", field.label(), edited);
+
+    set_field(query_params, field, edited);
+    if field == Field::BootVersion {
+        drop_incompatible_dependencies(&spring_metadata.dependencies.values, query_params);
     }
 
-    if deps {
-        let dependency_names = spring_metadata.dependencies.values
-            .iter()
-            .flat_map(|category| category.values.iter())
-            .collect::<Vec<_>>();
-
-        let dependency_choices = MultiSelect::new("Dependencies", dependency_names)
-            .with_page_size(5)
-            .with_keep_filter(true)
-            .with_help_message("🔍 Type to search • Space to select • ↑↓ to navigate • Enter to confirm")
-            .with_render_config(base_config("🧩"))
-            .prompt()
-            .with_context(|| "Failed to get input for Dependencies")?;
-        debug!("Selected Dependencies: {:?}", dependency_choices);
-
-        let _dependencies = dependency_choices
-            .iter()
-            .map(|dep| dep.id.clone())
-            .collect::<Vec<_>>()
-            .join(",");
-            
-        dependencies = Some(_dependencies);
+    Ok(())
+}
+
+/// Sets a single field. The base dir follows the artifact id unless it was chosen separately,
+/// and a package name equal to the derived suggestion is left to the Initializr.
+pub fn set_field(query_params: &mut QueryParam, field: Field, value: String) {
+    match field {
+        Field::GroupId => query_params.group_id = value,
+        Field::ArtifactId => {
+            if query_params.base_dir == query_params.artifact_id {
+                query_params.base_dir = value.clone();
+            }
+            query_params.artifact_id = value;
+        }
+        Field::Name => query_params.name = value,
+        Field::Description => query_params.description = value,
+        Field::PackageName => {
+            let derived = derive_package_name(&query_params.group_id, &query_params.artifact_id);
+            query_params.package_name = (value != derived).then_some(value);
+        }
+        Field::ProjectType => query_params.project_type = value,
+        Field::Language => query_params.language = value,
+        Field::BootVersion => query_params.boot_version = value,
+        Field::Packaging => query_params.packaging = value,
+        Field::JavaVersion => query_params.java_version = value,
+        Field::Dependencies => query_params.dependencies = value,
     }
+}
 
-    if maven {
-        project_type = "maven-project".to_string();
+fn drop_incompatible_dependencies(categories: &[DependencyCategories], query_params: &mut QueryParam) {
+    let Ok(version) = query_params.boot_version.parse::<Version>() else {
+        return;
+    };
+
+    let (kept, dropped): (Vec<_>, Vec<_>) = query_params.dependencies.split(',')
+        .filter(|id| !id.is_empty())
+        .partition(|id| {
+            categories.iter()
+                .flat_map(|category| category.values.iter())
+                .find(|dep| dep.id == *id)
+                .is_none_or(|dep| dep.is_compatible_with(&version))
+        });
+    if !dropped.is_empty() {
+        println!("Removed the dependencies incompatible with Spring Boot {}: {}", query_params.boot_version, dropped.join(", "));
+        query_params.dependencies = kept.join(",");
+    }
+}
+
+/// Position of the value whose id or name matches `preferred`, 0 when nothing matches.
+fn preferred_index(values: &[Value], preferred: Option<&str>) -> usize {
+    let Some(preferred) = preferred else {
+        return 0;
+    };
+
+    let index = values.iter().position(|value| {
+        value.id.eq_ignore_ascii_case(preferred)
+            || value.name.eq_ignore_ascii_case(preferred)
+            || normalize_boot_version(&value.id) == normalize_boot_version(preferred)
+    });
+    if index.is_none() {
+        debug!("The configured default '{}' is not one of the available options", preferred);
     }
 
-    Ok(QueryParam {
-        project_type,
-        language: spring_metadata.language.default,
-        boot_version: spring_metadata.boot_version.default.replace(".RELEASE", "").replace(".M", "-M").replace(".RC", "-RC"),
-        group_id,
-        artifact_id: artifact_id.clone(),
-        name: name.unwrap_or(spring_metadata.name.default),
-        description: description.unwrap_or(spring_metadata.description.default),
-        packaging: spring_metadata.packaging.default,
-        java_version: spring_metadata.java_version.default,
-        dependencies: dependencies.unwrap_or("".to_string()),
-        base_dir: artifact_id,
-    })
+    index.unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query_params() -> QueryParam {
+        QueryParam {
+            project_type: "gradle-project".to_string(),
+            language: "java".to_string(),
+            boot_version: "3.5.6".to_string(),
+            group_id: "com.example".to_string(),
+            artifact_id: "demo".to_string(),
+            name: "demo".to_string(),
+            description: "Demo project for Spring Boot".to_string(),
+            package_name: None,
+            packaging: "jar".to_string(),
+            java_version: "17".to_string(),
+            dependencies: String::new(),
+            base_dir: "demo".to_string(),
+        }
+    }
+
+    #[test]
+    fn suggested_package_follows_the_ids_and_is_not_sent() {
+        let mut params = query_params();
+        set_field(&mut params, Field::ArtifactId, "user-service".to_string());
+
+        assert_eq!(params.package_name, None);
+        assert_eq!(Field::PackageName.value(&params), "com.example.userservice");
+        assert_eq!(params.base_dir, "user-service");
 
+        set_field(&mut params, Field::PackageName, "com.example.userservice".to_string());
+        assert_eq!(params.package_name, None);
+    }
+
+    #[test]
+    fn chosen_package_and_base_dir_stay_when_the_ids_change() {
+        let mut params = query_params();
+        set_field(&mut params, Field::PackageName, "org.acme.api".to_string());
+        params.base_dir = "services".to_string();
+
+        set_field(&mut params, Field::GroupId, "io.other".to_string());
+        set_field(&mut params, Field::ArtifactId, "other".to_string());
+
+        assert_eq!(params.package_name.as_deref(), Some("org.acme.api"));
+        assert_eq!(params.base_dir, "services");
+    }
 }
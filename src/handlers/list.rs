@@ -1,24 +1,47 @@
 use std::io::{BufWriter, Write}; 
 use anyhow::{anyhow, Context, Result};
-use crate::{client::spring_initializr::get_metadata, models::list::Lists::{self, Categories, Values}};
+use crate::{cli::root::OutputFormat, client::spring_initializr::SpringInitializr, models::list::Lists::{self, Categories, Values}};
 
-pub async fn get_lists(item: &str) -> Result<Lists>{
-    let spring_metadata = get_metadata().await
+pub async fn get_lists(initializr: &SpringInitializr, item: &str) -> Result<Lists>{
+    let spring_metadata = initializr.get_metadata().await
         .with_context(|| "Failed to get metadata for listing out versions")?;
 
     match item {
-        "java" => Ok(Values(spring_metadata.java_version.values)),
-        "boot" => Ok(Values(spring_metadata.boot_version.values)),
-        "project_type" => Ok(Values(spring_metadata.project_type.values)),
-        "language" => Ok(Values(spring_metadata.language.values)),
+        "java" => Ok(Values(spring_metadata.java_version)),
+        "boot" => Ok(Values(spring_metadata.boot_version)),
+        "project_type" => Ok(Values(spring_metadata.project_type)),
+        "language" => Ok(Values(spring_metadata.language)),
         "deps" => Ok(Categories(spring_metadata.dependencies.values)),
         _ => Err(anyhow!("Unknown item '{}'", item))
     }
 }
 
+pub fn print_list<W: Write>(buf: &mut BufWriter<W>, title: &str, list: Lists, format: OutputFormat, details: bool) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            writeln!(buf, "{}", title)?;
+            match list {
+                Values(_) => print_values(buf, list)?,
+                Categories(_) => {
+                    writeln!(buf, " ")?;
+                    print_categories(buf, list, details)?;
+                }
+            }
+        }
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *buf, &list)?;
+            writeln!(buf)?;
+        }
+        OutputFormat::Yaml => serde_norway::to_writer(&mut *buf, &list)?,
+        OutputFormat::Csv => print_csv(buf, &list)?,
+    }
+
+    Ok(())
+}
+
 pub fn print_values<W: Write>(buf: &mut BufWriter<W>, versions: Lists) -> Result<()>{
     if let Values(vers) = versions {
-        for version in vers {
+        for version in vers.values {
             writeln!(buf, "{}", version)?;
         }
     }else {
@@ -28,12 +51,26 @@ pub fn print_values<W: Write>(buf: &mut BufWriter<W>, versions: Lists) -> Result
     Ok(())
 }
 
-pub fn print_categories<W: Write>(buf: &mut BufWriter<W>, categories: Lists) -> Result<()> {
+pub fn print_categories<W: Write>(buf: &mut BufWriter<W>, categories: Lists, details: bool) -> Result<()> {
     if let Categories(cats) = categories {
         for category in cats {
             writeln!(buf, "{}:", category.name)?;
             for dep in category.values {
-                writeln!(buf, "-{}", dep)?;
+                if !details {
+                    writeln!(buf, "-{}", dep)?;
+                    continue;
+                }
+
+                writeln!(buf, "-{} ({})", dep, dep.id)?;
+                if let Some(description) = &dep.description {
+                    writeln!(buf, "    {}", description)?;
+                }
+                for (rel, link) in dep.links() {
+                    match &link.title {
+                        Some(title) => writeln!(buf, "    {}: {} ({})", rel, link.href, title)?,
+                        None => writeln!(buf, "    {}: {}", rel, link.href)?,
+                    }
+                }
             }
             writeln!(buf, " ")?;
         }
@@ -43,3 +80,40 @@ pub fn print_categories<W: Write>(buf: &mut BufWriter<W>, categories: Lists) ->
 
     Ok(())
 }
+
+pub fn print_csv<W: Write>(buf: &mut BufWriter<W>, list: &Lists) -> Result<()> {
+    match list {
+        Values(values) => {
+            writeln!(buf, "id,name,default")?;
+            for value in &values.values {
+                writeln!(buf, "{},{},{}", csv_field(&value.id), csv_field(&value.name), value.id == values.default)?;
+            }
+        }
+        Categories(categories) => {
+            writeln!(buf, "category,id,name,description,version_range")?;
+            for category in categories {
+                for dep in &category.values {
+                    writeln!(
+                        buf,
+                        "{},{},{},{},{}",
+                        csv_field(&category.name),
+                        csv_field(&dep.id),
+                        csv_field(&dep.name),
+                        csv_field(dep.description.as_deref().unwrap_or("")),
+                        csv_field(dep.version_range.as_deref().unwrap_or("")),
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
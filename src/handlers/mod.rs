@@ -1,2 +1,18 @@
+pub mod add;
+pub mod answers;
+pub mod build_file;
+pub mod config;
+pub mod dependency_picker;
+pub mod dry_run;
+pub mod generate;
+pub mod git;
+pub mod hooks;
 pub mod interactive;
 pub mod list;
+pub mod non_interactive;
+pub mod preset;
+pub mod resolve;
+pub mod search;
+pub mod share_url;
+pub mod tui;
+pub mod validation;
@@ -0,0 +1,18 @@
+use anyhow::{Context, Result};
+use tracing::{debug, trace};
+use crate::{
+    client::spring_initializr::SpringInitializr,
+    handlers::resolve::resolve_query_params,
+    models::{answers::Answers, spring::QueryParam},
+};
+
+pub async fn non_interactivity(initializr: &SpringInitializr, answers: Answers) -> Result<QueryParam> {
+    let spring_metadata = initializr.get_metadata().await
+        .with_context(|| "Failed to get the metadata")?;
+    trace!("Spring Metadata: {:?}", spring_metadata);
+
+    let query_params = resolve_query_params(&spring_metadata, answers)?;
+    debug!("Resolved query params: {:?}", query_params);
+
+    Ok(query_params)
+}
@@ -0,0 +1,388 @@
+use std::{env, fs, io::{BufWriter, Write}, path::{Path, PathBuf}};
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use tracing::{debug, info, warn};
+use crate::{
+    cli::root::OutputFormat,
+    handlers::{answers::{read_file, write_file}, config::config_path, list::csv_field},
+    models::answers::Answers,
+};
+
+const PRESET_EXTENSION: &str = "toml";
+const PRESET_PATH_VAR: &str = "XSPRING_PRESET_PATH";
+
+/// Where a preset directory was configured, in order of precedence.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PresetSource {
+    User,
+    Env,
+    Config,
+}
+
+impl PresetSource {
+    fn label(self) -> &'static str {
+        match self {
+            PresetSource::User => "user",
+            PresetSource::Env => "env",
+            PresetSource::Config => "config",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PresetDir {
+    pub path: PathBuf,
+    pub source: PresetSource,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Preset {
+    pub name: String,
+    pub source: PresetSource,
+    pub path: PathBuf,
+    /// The preset of the same name that wins over this one
+    #[serde(rename = "shadowedBy", skip_serializing_if = "Option::is_none")]
+    pub shadowed_by: Option<PathBuf>,
+    #[serde(flatten)]
+    pub answers: Answers,
+}
+
+/// Presets saved with `preset save` live next to the config file, in `presets/<name>.toml`.
+pub fn presets_dir() -> Result<PathBuf> {
+    let config_path = config_path()?;
+    let config_dir = config_path.parent()
+        .ok_or_else(|| anyhow!("Could not determine the directory of the config file {:?}", config_path))?;
+
+    Ok(config_dir.join("presets"))
+}
+
+/// The user's own presets first, then every `XSPRING_PRESET_PATH` entry, then the `presetPaths` of the config.
+/// The first directory that has a preset of a given name wins.
+pub fn preset_dirs(config_paths: &[PathBuf]) -> Result<Vec<PresetDir>> {
+    let mut dirs = vec![PresetDir { path: presets_dir()?, source: PresetSource::User }];
+
+    if let Some(paths) = env::var_os(PRESET_PATH_VAR) {
+        dirs.extend(env::split_paths(&paths)
+            .filter(|path| !path.as_os_str().is_empty())
+            .map(|path| PresetDir { path, source: PresetSource::Env }));
+    }
+
+    let config_path = config_path()?;
+    let config_dir = config_path.parent().unwrap_or(Path::new(""));
+    dirs.extend(config_paths.iter().map(|path| PresetDir { path: config_dir.join(path), source: PresetSource::Config }));
+
+    for dir in dirs.iter().filter(|dir| dir.source != PresetSource::User && !dir.path.is_dir()) {
+        warn!("The {} preset directory {:?} does not exist", dir.source.label(), dir.path);
+    }
+    debug!("Preset directories: {:?}", dirs);
+
+    Ok(dirs)
+}
+
+pub fn load_preset(name: &str, dirs: &[PresetDir]) -> Result<Answers> {
+    validate_preset_name(name)?;
+    let mut found = dirs.iter().map(|dir| preset_file(&dir.path, name)).filter(|path| path.is_file());
+    let Some(path) = found.next() else {
+        return Err(anyhow!("No preset named '{}' exists, 'xspring preset list' shows the available presets", name));
+    };
+    for shadowed in found {
+        warn!("The preset '{}' in {:?} is shadowed by {:?}", name, shadowed, path);
+    }
+
+    let answers = read_file::<Answers>(&path)
+        .with_context(|| format!("Failed to load the preset {:?}", path))?;
+    debug!("Loaded preset '{}' from {:?}: {:?}", name, path, answers);
+
+    Ok(answers)
+}
+
+/// Saves to the user's preset directory, shared directories are never written to.
+pub fn save_preset(name: &str, answers: &Answers, overwrite: bool, dirs: &[PresetDir]) -> Result<()> {
+    if answers.is_empty() {
+        return Err(anyhow!("Nothing to save, pass the values of the preset as flags (e.g. -d web,actuator -p jar) or with --from"));
+    }
+
+    validate_preset_name(name)?;
+    let path = preset_file(&presets_dir()?, name);
+    if path.exists() && !overwrite {
+        return Err(anyhow!("The preset '{}' already exists at {:?}, pass '--overwrite' to replace it", name, path));
+    }
+
+    write_file(&path, answers)
+        .with_context(|| format!("Failed to save the preset {:?}", path))?;
+    info!("Saved preset '{}' to {:?}", name, path);
+    println!("Saved preset '{}' to {:?}", name, path);
+
+    for shared in shared_preset_files(name, dirs) {
+        println!("It takes precedence over the preset of the same name in {:?}", shared);
+    }
+
+    Ok(())
+}
+
+pub fn delete_preset(name: &str, dirs: &[PresetDir]) -> Result<()> {
+    validate_preset_name(name)?;
+    let path = preset_file(&presets_dir()?, name);
+    if !path.exists() {
+        return match shared_preset_files(name, dirs).first() {
+            Some(shared) => Err(anyhow!("The preset '{}' comes from the shared directory {:?}, which xspring does not modify", name, shared)),
+            None => Err(anyhow!("No preset named '{}' exists", name)),
+        };
+    }
+
+    fs::remove_file(&path)
+        .with_context(|| format!("Failed to delete the preset {:?}", path))?;
+    info!("Deleted preset '{}' at {:?}", name, path);
+    println!("Deleted preset '{}'", name);
+
+    Ok(())
+}
+
+/// Every preset of every directory, sorted by name and then precedence. Presets that lose
+/// a name conflict are kept, with `shadowed_by` pointing at the one that is used.
+/// Files that cannot be read or parsed are skipped with a warning.
+pub fn list_presets(dirs: &[PresetDir]) -> Result<Vec<Preset>> {
+    let mut presets = Vec::new();
+    for (precedence, dir) in dirs.iter().enumerate() {
+        if !dir.path.is_dir() {
+            debug!("No preset directory at {:?}", dir.path);
+            continue;
+        }
+
+        let entries = fs::read_dir(&dir.path)
+            .with_context(|| format!("Failed to read the preset directory {:?}", dir.path))?;
+        for entry in entries {
+            let path = entry.with_context(|| format!("Failed to read the preset directory {:?}", dir.path))?.path();
+            let Some(name) = preset_name(&path) else {
+                continue;
+            };
+
+            // One broken file should not hide every other preset
+            let answers = match read_file::<Answers>(&path) {
+                Ok(answers) => answers,
+                Err(err) => {
+                    warn!("Skipping the preset {:?}, it could not be loaded: {:?}", path, err);
+                    eprintln!("Skipping the preset {:?}, it could not be loaded: {:#}", path, err);
+                    continue;
+                }
+            };
+            presets.push((precedence, Preset { name, source: dir.source, path, shadowed_by: None, answers }));
+        }
+    }
+    presets.sort_by(|(a_precedence, a), (b_precedence, b)| a.name.cmp(&b.name).then(a_precedence.cmp(b_precedence)));
+
+    let mut presets = presets.into_iter().map(|(_, preset)| preset).collect::<Vec<_>>();
+    for index in 1..presets.len() {
+        let (winners, rest) = presets.split_at_mut(index);
+        let previous = &winners[index - 1];
+        if previous.name == rest[0].name {
+            let winner = previous.shadowed_by.clone().unwrap_or_else(|| previous.path.clone());
+            warn!("The preset '{}' in {:?} is shadowed by {:?}", rest[0].name, rest[0].path, winner);
+            rest[0].shadowed_by = Some(winner);
+        }
+    }
+
+    Ok(presets)
+}
+
+pub fn print_presets<W: Write>(buf: &mut BufWriter<W>, presets: &[Preset], format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            if presets.is_empty() {
+                writeln!(buf, "No presets saved yet, create one with 'xspring preset save <name>'")?;
+                return Ok(());
+            }
+
+            let name_width = presets.iter().map(|preset| preset.name.len()).max().unwrap_or(0);
+            let source_width = presets.iter().map(|preset| preset.source.label().len()).max().unwrap_or(0);
+            for preset in presets {
+                writeln!(buf, "{:name_width$}  {:source_width$}  {}", preset.name, preset.source.label(), summary(&preset.answers))?;
+                match preset.shadowed_by.as_deref() {
+                    Some(winner) => writeln!(buf, "{:name_width$}  {:source_width$}  {:?}, shadowed by {:?}", "", "", preset.path, winner)?,
+                    None if preset.source != PresetSource::User => writeln!(buf, "{:name_width$}  {:source_width$}  {:?}", "", "", preset.path)?,
+                    None => {}
+                }
+            }
+        }
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *buf, presets)?;
+            writeln!(buf)?;
+        }
+        OutputFormat::Yaml => serde_norway::to_writer(&mut *buf, presets)?,
+        OutputFormat::Csv => {
+            writeln!(buf, "name,source,type,language,packaging,javaVersion,dependencies,path,shadowedBy")?;
+            for preset in presets {
+                let answers = &preset.answers;
+                writeln!(
+                    buf,
+                    "{},{},{},{},{},{},{},{},{}",
+                    csv_field(&preset.name),
+                    csv_field(preset.source.label()),
+                    csv_field(answers.project_type.as_deref().unwrap_or_default()),
+                    csv_field(answers.language.as_deref().unwrap_or_default()),
+                    csv_field(answers.packaging.as_deref().unwrap_or_default()),
+                    csv_field(answers.java_version.as_deref().unwrap_or_default()),
+                    csv_field(answers.dependencies.as_deref().unwrap_or_default()),
+                    csv_field(&preset.path.display().to_string()),
+                    csv_field(&preset.shadowed_by.as_deref().map(|path| path.display().to_string()).unwrap_or_default()),
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn print_preset<W: Write>(buf: &mut BufWriter<W>, answers: &Answers, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Table => write!(buf, "{}", toml::to_string_pretty(answers)?)?,
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *buf, answers)?;
+            writeln!(buf)?;
+        }
+        OutputFormat::Yaml => serde_norway::to_writer(&mut *buf, answers)?,
+        OutputFormat::Csv => {
+            writeln!(buf, "key,value")?;
+            if let serde_json::Value::Object(values) = serde_json::to_value(answers)? {
+                for (key, value) in values {
+                    writeln!(buf, "{},{}", csv_field(&key), csv_field(value.as_str().unwrap_or_default()))?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn preset_file(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.{}", name, PRESET_EXTENSION))
+}
+
+fn shared_preset_files(name: &str, dirs: &[PresetDir]) -> Vec<PathBuf> {
+    dirs.iter()
+        .filter(|dir| dir.source != PresetSource::User)
+        .map(|dir| preset_file(&dir.path, name))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+fn preset_name(path: &Path) -> Option<String> {
+    if path.extension().and_then(|extension| extension.to_str()) != Some(PRESET_EXTENSION) {
+        return None;
+    }
+
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .filter(|stem| validate_preset_name(stem).is_ok())
+        .map(|stem| stem.to_string())
+}
+
+// Names end up as file names, so only allow characters that are safe everywhere
+fn validate_preset_name(name: &str) -> Result<()> {
+    let valid = name.chars().next().is_some_and(|first| first.is_ascii_alphanumeric())
+        && name.chars().all(|character| character.is_ascii_alphanumeric() || matches!(character, '-' | '_' | '.'));
+    if !valid {
+        return Err(anyhow!("Invalid preset name '{}', use letters, digits, '-', '_' and '.' only", name));
+    }
+
+    Ok(())
+}
+
+fn summary(answers: &Answers) -> String {
+    let mut parts = [&answers.project_type, &answers.language, &answers.packaging]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>();
+    if let Some(boot_version) = answers.boot_version.as_deref() {
+        parts.push(format!("Spring Boot {}", boot_version));
+    }
+    if let Some(java_version) = answers.java_version.as_deref() {
+        parts.push(format!("Java {}", java_version));
+    }
+    if let Some(dependencies) = answers.dependencies.as_deref().filter(|dependencies| !dependencies.is_empty()) {
+        parts.push(format!("deps: {}", dependencies));
+    }
+
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    struct Dirs {
+        _temp: TempDir,
+        dirs: Vec<PresetDir>,
+    }
+
+    fn preset_dirs() -> Dirs {
+        let temp = tempfile::tempdir().unwrap();
+        let dirs = [("user", PresetSource::User), ("env", PresetSource::Env), ("config", PresetSource::Config)]
+            .into_iter()
+            .map(|(name, source)| {
+                let path = temp.path().join(name);
+                fs::create_dir(&path).unwrap();
+                PresetDir { path, source }
+            })
+            .collect();
+
+        Dirs { _temp: temp, dirs }
+    }
+
+    fn write_preset(dir: &PresetDir, name: &str, contents: &str) -> PathBuf {
+        let path = preset_file(&dir.path, name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn loads_the_preset_of_the_first_directory_that_has_it() {
+        let Dirs { _temp, dirs } = preset_dirs();
+        write_preset(&dirs[1], "api", "packaging = \"war\"\n");
+        write_preset(&dirs[2], "api", "packaging = \"jar\"\n");
+        write_preset(&dirs[2], "batch", "language = \"kotlin\"\n");
+
+        assert_eq!(load_preset("api", &dirs).unwrap().packaging.as_deref(), Some("war"));
+        assert_eq!(load_preset("batch", &dirs).unwrap().language.as_deref(), Some("kotlin"));
+
+        write_preset(&dirs[0], "api", "packaging = \"ear\"\n");
+        assert_eq!(load_preset("api", &dirs).unwrap().packaging.as_deref(), Some("ear"));
+        assert!(load_preset("missing", &dirs).is_err());
+    }
+
+    #[test]
+    fn lists_shadowed_presets_after_the_one_that_is_used() {
+        let Dirs { _temp, dirs } = preset_dirs();
+        let config = write_preset(&dirs[2], "api", "packaging = \"jar\"\n");
+        let env = write_preset(&dirs[1], "api", "packaging = \"war\"\n");
+        let user = write_preset(&dirs[0], "api", "packaging = \"ear\"\n");
+        write_preset(&dirs[2], "batch", "language = \"kotlin\"\n");
+
+        let presets = list_presets(&dirs).unwrap();
+        let listed = presets.iter()
+            .map(|preset| (preset.name.as_str(), preset.source, &preset.path, preset.shadowed_by.as_ref()))
+            .collect::<Vec<_>>();
+        assert_eq!(listed, [
+            ("api", PresetSource::User, &user, None),
+            ("api", PresetSource::Env, &env, Some(&user)),
+            ("api", PresetSource::Config, &config, Some(&user)),
+            ("batch", PresetSource::Config, &dirs[2].path.join("batch.toml"), None),
+        ]);
+    }
+
+    #[test]
+    fn skips_presets_that_cannot_be_loaded() {
+        let Dirs { _temp, dirs } = preset_dirs();
+        write_preset(&dirs[0], "broken", "packaging = [\n");
+        write_preset(&dirs[1], "typed", "packaging = 1\n");
+        fs::create_dir(preset_file(&dirs[1].path, "unreadable")).unwrap();
+        write_preset(&dirs[2], "api", "packaging = \"jar\"\n");
+        fs::write(dirs[2].path.join("notes.txt"), "not a preset").unwrap();
+
+        let names = list_presets(&dirs).unwrap().into_iter().map(|preset| preset.name).collect::<Vec<_>>();
+        assert_eq!(names, ["api"]);
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::{anyhow, Result};
+use crate::{
+    handlers::validation::{validate_artifact_id, validate_group_id, validate_package_name},
+    models::{
+        answers::Answers,
+        spring::{normalize_boot_version, DependencyCategories, InitializrMetadata, MetadataValue, QueryParam},
+        version::Version,
+    },
+};
+
+pub fn resolve_query_params(metadata: &InitializrMetadata, answers: Answers) -> Result<QueryParam> {
+    let boot_version = resolve_boot_version(answers.boot_version.as_deref(), &metadata.boot_version)?;
+    let dependencies = resolve_dependencies(answers.dependencies.as_deref().unwrap_or(""), &metadata.dependencies.values, &boot_version)?;
+
+    let group_id = answers.group_id.unwrap_or_else(|| metadata.group_id.default.clone());
+    validate_group_id(&group_id)?;
+
+    let artifact_id = answers.artifact_id.unwrap_or_else(|| metadata.artifact_id.default.clone());
+    validate_artifact_id(&artifact_id)?;
+
+    if let Some(package_name) = answers.package_name.as_deref() {
+        validate_package_name(package_name)?;
+    }
+
+    Ok(QueryParam {
+        project_type: resolve_value("project type", answers.project_type.as_deref(), &metadata.project_type)?,
+        language: resolve_value("language", answers.language.as_deref(), &metadata.language)?,
+        boot_version,
+        group_id,
+        artifact_id: artifact_id.clone(),
+        name: answers.name.unwrap_or_else(|| metadata.name.default.clone()),
+        description: answers.description.unwrap_or_else(|| metadata.description.default.clone()),
+        package_name: answers.package_name,
+        packaging: resolve_value("packaging", answers.packaging.as_deref(), &metadata.packaging)?,
+        java_version: resolve_value("Java version", answers.java_version.as_deref(), &metadata.java_version)?,
+        dependencies,
+        base_dir: answers.base_dir.unwrap_or(artifact_id),
+    })
+}
+
+pub fn resolve_value(field: &str, input: Option<&str>, metadata: &MetadataValue) -> Result<String> {
+    let Some(input) = input else {
+        return Ok(metadata.default.clone());
+    };
+
+    metadata.values.iter()
+        .find(|value| value.id.eq_ignore_ascii_case(input) || value.name.eq_ignore_ascii_case(input))
+        .map(|value| value.id.clone())
+        .ok_or_else(|| anyhow!(
+            "Unknown {} '{}'. Available values: {}",
+            field,
+            input,
+            metadata.values.iter().map(|value| value.id.as_str()).collect::<Vec<_>>().join(", ")
+        ))
+}
+
+pub fn resolve_boot_version(input: Option<&str>, metadata: &MetadataValue) -> Result<String> {
+    let Some(input) = input else {
+        return Ok(normalize_boot_version(&metadata.default));
+    };
+
+    let wanted = normalize_boot_version(input);
+    metadata.values.iter()
+        .map(|value| normalize_boot_version(&value.id))
+        .find(|version| version.eq_ignore_ascii_case(&wanted))
+        .ok_or_else(|| anyhow!(
+            "Unknown Spring Boot version '{}'. Available versions: {}",
+            input,
+            metadata.values.iter().map(|value| normalize_boot_version(&value.id)).collect::<Vec<_>>().join(", ")
+        ))
+}
+
+pub fn resolve_dependencies(input: &str, categories: &[DependencyCategories], boot_version: &str) -> Result<String> {
+    let mut dependencies = Vec::new();
+    let parsed_boot_version = boot_version.parse::<Version>().ok();
+
+    for id in input.split(',').map(|id| id.trim()).filter(|id| !id.is_empty()) {
+        let dep = categories.iter()
+            .flat_map(|category| category.values.iter())
+            .find(|dep| dep.id == id)
+            .ok_or_else(|| anyhow!("Unknown dependency '{}'. Run 'xspring list --deps' to see the available dependencies", id))?;
+
+        if let Some(version) = &parsed_boot_version
+            && !dep.is_compatible_with(version)
+        {
+            let range = dep.version_range()
+                .map(|range| range.to_string())
+                .unwrap_or_default();
+            return Err(anyhow!(
+                "Dependency '{}' ({}) is not compatible with Spring Boot {}, it requires Spring Boot {}",
+                dep.id, dep.name, boot_version, range
+            ));
+        }
+        if !dependencies.contains(&id) {
+            dependencies.push(id);
+        }
+    }
+
+    Ok(dependencies.join(","))
+}
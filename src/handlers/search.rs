@@ -0,0 +1,192 @@
+use std::io::{BufWriter, Write};
+use anyhow::{anyhow, Result};
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use serde::Serialize;
+use tracing::debug;
+use crate::{cli::root::OutputFormat, handlers::list::csv_field, models::spring::{DependencyCategories, Value}};
+
+#[derive(Serialize, Debug)]
+pub struct SearchResult {
+    pub score: i64,
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Fuzzy matches every word of `term` against the id, name, category and description of every dependency.
+/// Descriptions are matched word by word and weighted down, so that hits on the id or
+/// name rank first and long descriptions do not match every term.
+pub fn search_dependencies(categories: Vec<DependencyCategories>, term: &str, category: Option<&str>) -> Result<Vec<SearchResult>> {
+    let categories = match category {
+        Some(wanted) => {
+            let wanted = wanted.to_lowercase();
+            let (matching, others): (Vec<_>, Vec<_>) = categories.into_iter()
+                .partition(|category| category.name.to_lowercase().contains(&wanted));
+            if matching.is_empty() {
+                return Err(anyhow!(
+                    "No dependency category matches '{}'. Available categories: {}",
+                    wanted,
+                    others.iter().map(|category| category.name.as_str()).collect::<Vec<_>>().join(", ")
+                ));
+            }
+            matching
+        }
+        None => categories,
+    };
+
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut results = Vec::new();
+    for category in categories {
+        for dep in category.values.iter() {
+            if let Some(score) = match_score(&matcher, &category.name, dep, term) {
+                results.push(SearchResult {
+                    score,
+                    id: dep.id.clone(),
+                    name: dep.name.clone(),
+                    category: category.name.clone(),
+                    description: dep.description.clone(),
+                });
+            }
+        }
+    }
+
+    results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.id.cmp(&b.id)));
+    debug!("Found {} dependencies matching '{}'", results.len(), term);
+
+    Ok(results)
+}
+
+pub fn print_search_results<W: Write>(buf: &mut BufWriter<W>, results: &[SearchResult], format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            if results.is_empty() {
+                writeln!(buf, "No matching dependencies found")?;
+                return Ok(());
+            }
+
+            let id_width = results.iter().map(|result| result.id.len()).max().unwrap_or(0);
+            let name_width = results.iter().map(|result| result.name.len()).max().unwrap_or(0);
+            for result in results {
+                writeln!(buf, "{:id_width$}  {:name_width$}  ({})", result.id, result.name, result.category)?;
+            }
+        }
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *buf, results)?;
+            writeln!(buf)?;
+        }
+        OutputFormat::Yaml => serde_norway::to_writer(&mut *buf, results)?,
+        OutputFormat::Csv => {
+            writeln!(buf, "score,id,name,category")?;
+            for result in results {
+                writeln!(buf, "{},{},{},{}", result.score, csv_field(&result.id), csv_field(&result.name), csv_field(&result.category))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Sum of the best scores of every word of `term`, `None` unless each word matches somewhere,
+/// so that `reactive web` finds a dependency described as "Build reactive web applications".
+pub fn match_score(matcher: &SkimMatcherV2, category: &str, dep: &Value, term: &str) -> Option<i64> {
+    term.split_whitespace()
+        .map(|word| word_score(matcher, category, dep, word))
+        .sum()
+}
+
+/// Best fuzzy score of `word` against the id, name, category and description words of `dep`.
+/// Category and description matches count half.
+fn word_score(matcher: &SkimMatcherV2, category: &str, dep: &Value, word: &str) -> Option<i64> {
+    [
+        matcher.fuzzy_match(&dep.id, word),
+        matcher.fuzzy_match(&dep.name, word),
+        matcher.fuzzy_match(category, word).map(|score| score / 2),
+        dep.description.as_deref()
+            .and_then(|description| {
+                description.split_whitespace()
+                    .filter_map(|description_word| matcher.fuzzy_match(description_word, word))
+                    .max()
+            })
+            .map(|score| score / 2),
+    ]
+        .into_iter()
+        .flatten()
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use super::*;
+
+    fn dep(id: &str, name: &str, description: &str) -> Value {
+        Value {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            version_range: None,
+            links: BTreeMap::new(),
+        }
+    }
+
+    fn categories() -> Vec<DependencyCategories> {
+        vec![
+            DependencyCategories {
+                name: "Web".to_string(),
+                values: vec![
+                    dep("web", "Spring Web", "Build web, including RESTful, applications using Spring MVC."),
+                    dep("webflux", "Spring Reactive Web", "Build reactive web applications with Spring WebFlux and Netty."),
+                ],
+            },
+            DependencyCategories {
+                name: "SQL".to_string(),
+                values: vec![
+                    dep("data-jpa", "Spring Data JPA", "Persist data in SQL stores with Java Persistence API using Hibernate."),
+                    dep("flyway", "Flyway Migration", "Version control for your database so you can migrate from any version."),
+                ],
+            },
+        ]
+    }
+
+    fn ids(term: &str, category: Option<&str>) -> Vec<String> {
+        search_dependencies(categories(), term, category).unwrap()
+            .into_iter()
+            .map(|result| result.id)
+            .collect()
+    }
+
+    #[test]
+    fn matches_ids_names_and_descriptions() {
+        assert_eq!(ids("data-jpa", None), ["data-jpa"]);
+        assert_eq!(ids("Flyway Migration", None), ["flyway"]);
+        assert_eq!(ids("hibernate", None), ["data-jpa"]);
+        assert!(ids("kafka", None).is_empty());
+    }
+
+    #[test]
+    fn matches_multi_word_terms_word_by_word() {
+        assert_eq!(ids("reactive netty", None), ["webflux"]);
+        assert_eq!(ids("database migrate", None), ["flyway"]);
+        assert!(ids("reactive hibernate", None).is_empty());
+    }
+
+    #[test]
+    fn ranks_id_and_name_matches_above_description_matches() {
+        let results = search_dependencies(categories(), "web", None).unwrap();
+
+        assert_eq!(results[0].id, "web");
+        assert_eq!(results[1].id, "webflux");
+        assert!(results.windows(2).all(|pair| pair[0].score >= pair[1].score));
+    }
+
+    #[test]
+    fn filters_by_category() {
+        assert_eq!(ids("spring", Some("sql")), ["data-jpa"]);
+        assert!(ids("web", Some("SQL")).is_empty());
+
+        let err = search_dependencies(categories(), "web", Some("messaging")).unwrap_err();
+        assert_eq!(err.to_string(), "No dependency category matches 'messaging'. Available categories: Web, SQL");
+    }
+}
@@ -0,0 +1,171 @@
+use anyhow::{anyhow, Context, Result};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use tracing::{debug, trace};
+use url::{form_urlencoded, Url};
+use crate::models::answers::Answers;
+
+// Same characters the web UI leaves untouched through encodeURIComponent, plus ',' for dependency lists
+const FRAGMENT_VALUE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~')
+    .remove(b',');
+
+/// Parses a start.spring.io share link such as
+/// `https://start.spring.io/#!type=maven-project&dependencies=web` into the
+/// server it points at and the answers it carries.
+pub fn parse_share_url(input: &str) -> Result<(String, Answers)> {
+    let url = Url::parse(input.trim())
+        .with_context(|| format!("'{}' is not a valid URL", input))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(anyhow!("Unsupported URL scheme '{}', expected http or https", url.scheme()));
+    }
+    // Keep the path, self-hosted instances can live below a context path such as /initializr
+    let mut server_url = url.clone();
+    server_url.set_fragment(None);
+    server_url.set_query(None);
+    let server = server_url.as_str().trim_end_matches('/').to_string();
+
+    let fragment = url.fragment().unwrap_or("");
+    let params = fragment.strip_prefix('!').unwrap_or(fragment);
+    let params = if params.is_empty() { url.query().unwrap_or("") } else { params };
+    if params.is_empty() {
+        return Err(anyhow!("The URL '{}' does not contain any project configuration", input));
+    }
+
+    let mut answers = Answers::default();
+    for (key, value) in form_urlencoded::parse(params.as_bytes()) {
+        let value = Some(value.into_owned());
+        match key.as_ref() {
+            "type" => answers.project_type = value,
+            "language" => answers.language = value,
+            "platformVersion" | "bootVersion" => answers.boot_version = value,
+            "packaging" => answers.packaging = value,
+            "jvmVersion" | "javaVersion" => answers.java_version = value,
+            "groupId" => answers.group_id = value,
+            "artifactId" => answers.artifact_id = value,
+            "name" => answers.name = value,
+            "description" => answers.description = value,
+            "packageName" => answers.package_name = value,
+            "dependencies" => answers.dependencies = value,
+            "baseDir" => answers.base_dir = value,
+            _ => trace!("Ignoring unsupported share url parameter '{}'", key),
+        }
+    }
+    debug!("Parsed share url for {}: {:?}", server, answers);
+
+    Ok((server, answers))
+}
+
+/// Builds the share link the Initializr web UI understands for `answers`.
+pub fn share_url(server: &str, answers: &Answers) -> String {
+    let params = [
+        ("type", &answers.project_type),
+        ("language", &answers.language),
+        ("platformVersion", &answers.boot_version),
+        ("packaging", &answers.packaging),
+        ("jvmVersion", &answers.java_version),
+        ("groupId", &answers.group_id),
+        ("artifactId", &answers.artifact_id),
+        ("name", &answers.name),
+        ("description", &answers.description),
+        ("packageName", &answers.package_name),
+        ("dependencies", &answers.dependencies),
+    ];
+
+    let fragment = params.iter()
+        .filter_map(|(key, value)| value.as_ref().map(|value| (key, value)))
+        .map(|(key, value)| format!("{}={}", key, utf8_percent_encode(value, FRAGMENT_VALUE)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    format!("{}/#!{}", server.trim_end_matches('/'), fragment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_fragment_form() {
+        let (server, answers) = parse_share_url("https://start.spring.io/#!type=maven-project&language=kotlin&platformVersion=3.5.6&jvmVersion=21&dependencies=web,data-jpa,actuator").unwrap();
+
+        assert_eq!(server, "https://start.spring.io");
+        assert_eq!(answers.project_type.as_deref(), Some("maven-project"));
+        assert_eq!(answers.language.as_deref(), Some("kotlin"));
+        assert_eq!(answers.boot_version.as_deref(), Some("3.5.6"));
+        assert_eq!(answers.java_version.as_deref(), Some("21"));
+        assert_eq!(answers.dependencies.as_deref(), Some("web,data-jpa,actuator"));
+    }
+
+    #[test]
+    fn parses_the_query_form() {
+        let (server, answers) = parse_share_url("http://localhost:8080/?bootVersion=3.5.6&javaVersion=17&artifactId=demo").unwrap();
+
+        assert_eq!(server, "http://localhost:8080");
+        assert_eq!(answers.boot_version.as_deref(), Some("3.5.6"));
+        assert_eq!(answers.java_version.as_deref(), Some("17"));
+        assert_eq!(answers.artifact_id.as_deref(), Some("demo"));
+    }
+
+    #[test]
+    fn keeps_the_context_path_of_the_server() {
+        let (server, _) = parse_share_url("https://tools.mycorp.example/initializr/#!type=gradle-project").unwrap();
+        assert_eq!(server, "https://tools.mycorp.example/initializr");
+
+        let (server, _) = parse_share_url("https://tools.mycorp.example/initializr?type=gradle-project").unwrap();
+        assert_eq!(server, "https://tools.mycorp.example/initializr");
+    }
+
+    #[test]
+    fn decodes_encoded_values() {
+        let (_, answers) = parse_share_url("https://start.spring.io/#!name=My%20App&description=Orders%20%26%20payments%2C%20caf%C3%A9").unwrap();
+
+        assert_eq!(answers.name.as_deref(), Some("My App"));
+        assert_eq!(answers.description.as_deref(), Some("Orders & payments, café"));
+    }
+
+    #[test]
+    fn rejects_links_without_configuration() {
+        assert!(parse_share_url("https://start.spring.io/").is_err());
+        assert!(parse_share_url("ftp://start.spring.io/#!type=maven-project").is_err());
+        assert!(parse_share_url("not a url").is_err());
+    }
+
+    #[test]
+    fn builds_an_encoded_fragment() {
+        let answers = Answers {
+            project_type: Some("maven-project".to_string()),
+            name: Some("My App".to_string()),
+            description: Some("Orders & payments, café".to_string()),
+            dependencies: Some("web,data-jpa".to_string()),
+            base_dir: Some("ignored".to_string()),
+            ..Answers::default()
+        };
+
+        assert_eq!(
+            share_url("https://start.spring.io/", &answers),
+            "https://start.spring.io/#!type=maven-project&name=My%20App&description=Orders%20%26%20payments,%20caf%C3%A9&dependencies=web,data-jpa"
+        );
+    }
+
+    #[test]
+    fn share_url_round_trips_through_a_context_path() {
+        let answers = Answers {
+            group_id: Some("com.mycorp".to_string()),
+            artifact_id: Some("user-service".to_string()),
+            dependencies: Some("web,security".to_string()),
+            ..Answers::default()
+        };
+
+        let url = share_url("https://tools.mycorp.example/initializr", &answers);
+        assert_eq!(url, "https://tools.mycorp.example/initializr/#!groupId=com.mycorp&artifactId=user-service&dependencies=web,security");
+
+        let (server, parsed) = parse_share_url(&url).unwrap();
+        assert_eq!(server, "https://tools.mycorp.example/initializr");
+        assert_eq!(parsed.group_id, answers.group_id);
+        assert_eq!(parsed.artifact_id, answers.artifact_id);
+        assert_eq!(parsed.dependencies, answers.dependencies);
+    }
+}
@@ -0,0 +1,339 @@
+use std::io::{stdout, IsTerminal};
+use anyhow::{anyhow, Context, Result};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    DefaultTerminal,
+};
+use tracing::{debug, info, trace};
+use crate::{
+    cli::tui_ui::draw,
+    client::spring_initializr::SpringInitializr,
+    handlers::{
+        interactive::{set_field, Field},
+        resolve::resolve_query_params,
+        search::match_score,
+        validation::{validate_artifact_id, validate_group_id, validate_package_name},
+    },
+    models::{answers::Answers, spring::{normalize_boot_version, InitializrMetadata, MetadataValue, QueryParam, Value}, version::Version},
+};
+
+/// The fields of the form, in the order of start.spring.io. Dependencies have their own panel.
+pub const FORM_FIELDS: [Field; 10] = [
+    Field::ProjectType, Field::Language, Field::BootVersion, Field::GroupId, Field::ArtifactId,
+    Field::Name, Field::Description, Field::PackageName, Field::Packaging, Field::JavaVersion,
+];
+
+const PAGE_STEP: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    Form(usize),
+    Generate,
+    Dependencies,
+}
+
+pub struct DependencyRow<'a> {
+    pub category: &'a str,
+    pub dep: &'a Value,
+    pub selected: bool,
+    pub compatible: bool,
+}
+
+pub struct App<'a> {
+    pub metadata: &'a InitializrMetadata,
+    pub query_params: QueryParam,
+    pub focus: Focus,
+    pub search: String,
+    pub dependency_cursor: usize,
+    pub message: Option<String>,
+    matcher: SkimMatcherV2,
+}
+
+enum Exit {
+    Generate,
+    Quit,
+}
+
+/// Shows every option on one screen, starting from `answers` and then the configured `defaults`,
+/// and returns the options once the user generates.
+pub async fn tui_interactivity(initializr: &SpringInitializr, answers: Answers, defaults: &Answers) -> Result<QueryParam> {
+    if !stdout().is_terminal() {
+        return Err(anyhow!("The TUI needs a terminal, use 'xspring new' in scripts"));
+    }
+
+    let spring_metadata = initializr.get_metadata().await
+        .with_context(|| "Failed to get the metadata")?;
+    trace!("Spring Metadata: {:?}", spring_metadata);
+    let query_params = resolve_query_params(&spring_metadata, answers.or(defaults.clone()))?;
+
+    let mut app = App::new(&spring_metadata, query_params);
+    let mut terminal = ratatui::try_init()
+        .with_context(|| "Failed to set up the terminal")?;
+    let exit = app.run(&mut terminal);
+    ratatui::try_restore()
+        .with_context(|| "Failed to restore the terminal")?;
+
+    match exit? {
+        Exit::Generate => {
+            debug!("TUI query params: {:?}", app.query_params);
+            Ok(app.query_params)
+        }
+        Exit::Quit => {
+            info!("Generation aborted in the TUI");
+            Err(anyhow!("Aborted, no project was generated"))
+        }
+    }
+}
+
+impl<'a> App<'a> {
+    fn new(metadata: &'a InitializrMetadata, query_params: QueryParam) -> App<'a> {
+        App {
+            metadata,
+            query_params,
+            focus: Focus::Form(0),
+            search: String::new(),
+            dependency_cursor: 0,
+            message: None,
+            matcher: SkimMatcherV2::default().ignore_case(),
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<Exit> {
+        loop {
+            terminal.draw(|frame| draw(frame, self))
+                .with_context(|| "Failed to draw the TUI")?;
+
+            if let Event::Key(key) = event::read().with_context(|| "Failed to read a terminal event")?
+                && key.kind == KeyEventKind::Press
+                && let Some(exit) = self.handle_key(key)
+            {
+                return Ok(exit);
+            }
+        }
+    }
+
+    /// The metadata behind a field that is picked from a list, `None` for text fields.
+    pub fn choices(&self, field: Field) -> Option<&'a MetadataValue> {
+        match field {
+            Field::ProjectType => Some(&self.metadata.project_type),
+            Field::Language => Some(&self.metadata.language),
+            Field::BootVersion => Some(&self.metadata.boot_version),
+            Field::Packaging => Some(&self.metadata.packaging),
+            Field::JavaVersion => Some(&self.metadata.java_version),
+            _ => None,
+        }
+    }
+
+    /// The name of the chosen value for list fields, the text itself otherwise.
+    pub fn display_value(&self, field: Field) -> String {
+        let value = field.value(&self.query_params);
+        self.choices(field)
+            .and_then(|choices| choice_index(choices, field, &value).map(|index| choices.values[index].name.clone()))
+            .unwrap_or_else(|| value.to_string())
+    }
+
+    /// Dependencies matching the search, best match first, or every dependency by category
+    /// when there is no search.
+    pub fn dependency_rows(&self) -> Vec<DependencyRow<'a>> {
+        let version = self.query_params.boot_version.parse::<Version>().ok();
+        let selected = self.selected_ids();
+        let term = self.search.trim();
+
+        let mut rows = self.metadata.dependencies.values.iter()
+            .flat_map(|category| category.values.iter().map(move |dep| (category.name.as_str(), dep)))
+            .filter_map(|(category, dep)| {
+                let score = if term.is_empty() { Some(0) } else { match_score(&self.matcher, category, dep, term) };
+                score.map(|score| (score, DependencyRow {
+                    category,
+                    dep,
+                    selected: selected.contains(&dep.id.as_str()),
+                    compatible: version.as_ref().is_none_or(|version| dep.is_compatible_with(version)),
+                }))
+            })
+            .collect::<Vec<_>>();
+        // Stable, so an empty search keeps the category order
+        rows.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        rows.into_iter().map(|(_, row)| row).collect()
+    }
+
+    pub fn selected_ids(&self) -> Vec<&str> {
+        self.query_params.dependencies.split(',').filter(|id| !id.is_empty()).collect()
+    }
+
+    /// Everything that keeps the project from being generated, updated on every key press.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = [
+            validate_group_id(&self.query_params.group_id),
+            validate_artifact_id(&self.query_params.artifact_id),
+            validate_package_name(&Field::PackageName.value(&self.query_params)),
+        ]
+            .into_iter()
+            .filter_map(|result| result.err().map(|err| err.to_string()))
+            .collect::<Vec<_>>();
+
+        for field in [Field::Name, Field::Description] {
+            if field.value(&self.query_params).trim().is_empty() {
+                warnings.push(format!("{} cannot be empty", field.label()));
+            }
+        }
+
+        if let Ok(version) = self.query_params.boot_version.parse::<Version>() {
+            let selected = self.selected_ids();
+            let incompatible = self.metadata.dependencies.values.iter()
+                .flat_map(|category| category.values.iter())
+                .filter(|dep| selected.contains(&dep.id.as_str()) && !dep.is_compatible_with(&version));
+            for dep in incompatible {
+                let range = dep.version_range().map(|range| range.to_string()).unwrap_or_default();
+                warnings.push(format!("{} is not compatible with Spring Boot {}, it requires Spring Boot {}", dep.name, version, range));
+            }
+        }
+
+        warnings
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Option<Exit> {
+        self.message = None;
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Esc => return Some(Exit::Quit),
+            KeyCode::Char('c') if control => return Some(Exit::Quit),
+            KeyCode::Char('g') if control => return self.generate(),
+            KeyCode::Tab | KeyCode::BackTab => self.focus = self.other_panel(),
+            _ => match self.focus {
+                Focus::Form(index) => self.handle_form_key(index, key),
+                Focus::Generate => match key.code {
+                    KeyCode::Enter => return self.generate(),
+                    KeyCode::Up => self.focus = self.previous_focus(),
+                    KeyCode::Down => self.focus = self.next_focus(),
+                    _ => {}
+                },
+                Focus::Dependencies => self.handle_dependency_key(key),
+            },
+        }
+
+        None
+    }
+
+    fn handle_form_key(&mut self, index: usize, key: KeyEvent) {
+        let field = FORM_FIELDS[index];
+        match (key.code, self.choices(field)) {
+            (KeyCode::Up, _) => self.focus = self.previous_focus(),
+            (KeyCode::Down | KeyCode::Enter, _) => self.focus = self.next_focus(),
+            (KeyCode::Left, Some(choices)) => self.cycle_choice(field, choices, false),
+            (KeyCode::Right, Some(choices)) => self.cycle_choice(field, choices, true),
+            (KeyCode::Backspace, None) => {
+                let mut value = field.value(&self.query_params).to_string();
+                value.pop();
+                set_field(&mut self.query_params, field, value);
+            }
+            (KeyCode::Char(character), None) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let value = format!("{}{}", field.value(&self.query_params), character);
+                set_field(&mut self.query_params, field, value);
+            }
+            _ => {}
+        }
+    }
+
+    fn cycle_choice(&mut self, field: Field, choices: &MetadataValue, forward: bool) {
+        if choices.values.is_empty() {
+            return;
+        }
+
+        let current = choice_index(choices, field, &field.value(&self.query_params)).unwrap_or(0);
+        let count = choices.values.len();
+        let next = if forward { (current + 1) % count } else { (current + count - 1) % count };
+        let mut value = choices.values[next].id.clone();
+        if field == Field::BootVersion {
+            value = normalize_boot_version(&value);
+        }
+        debug!("{}: {}", field.label(), value);
+        set_field(&mut self.query_params, field, value);
+    }
+
+    fn handle_dependency_key(&mut self, key: KeyEvent) {
+        let count = self.dependency_rows().len();
+        match key.code {
+            KeyCode::Up => self.dependency_cursor = self.dependency_cursor.saturating_sub(1),
+            KeyCode::Down => self.dependency_cursor = (self.dependency_cursor + 1).min(count.saturating_sub(1)),
+            KeyCode::PageUp => self.dependency_cursor = self.dependency_cursor.saturating_sub(PAGE_STEP),
+            KeyCode::PageDown => self.dependency_cursor = (self.dependency_cursor + PAGE_STEP).min(count.saturating_sub(1)),
+            KeyCode::Char(' ') | KeyCode::Enter => self.toggle_dependency(),
+            KeyCode::Backspace => {
+                self.search.pop();
+                self.dependency_cursor = 0;
+            }
+            KeyCode::Char(character) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.search.push(character);
+                self.dependency_cursor = 0;
+            }
+            _ => {}
+        }
+    }
+
+    /// Incompatible dependencies can be removed but not added.
+    fn toggle_dependency(&mut self) {
+        let rows = self.dependency_rows();
+        let Some(row) = rows.get(self.dependency_cursor) else {
+            return;
+        };
+
+        let mut selected = self.selected_ids().into_iter().map(str::to_string).collect::<Vec<_>>();
+        if row.selected {
+            selected.retain(|id| *id != row.dep.id);
+        } else if row.compatible {
+            selected.push(row.dep.id.clone());
+        } else {
+            let range = row.dep.version_range().map(|range| range.to_string()).unwrap_or_default();
+            self.message = Some(format!("{} requires Spring Boot {}", row.dep.name, range));
+            return;
+        }
+
+        debug!("Dependencies: {:?}", selected);
+        self.query_params.dependencies = selected.join(",");
+    }
+
+    fn generate(&mut self) -> Option<Exit> {
+        if !self.warnings().is_empty() {
+            self.message = Some("Fix the problems below before generating".to_string());
+            return None;
+        }
+
+        Some(Exit::Generate)
+    }
+
+    /// There are only two panels, so Tab and Shift+Tab both jump to the other one.
+    fn other_panel(&self) -> Focus {
+        match self.focus {
+            Focus::Form(_) | Focus::Generate => Focus::Dependencies,
+            Focus::Dependencies => Focus::Form(0),
+        }
+    }
+
+    fn next_focus(&self) -> Focus {
+        match self.focus {
+            Focus::Form(index) if index + 1 < FORM_FIELDS.len() => Focus::Form(index + 1),
+            Focus::Form(_) => Focus::Generate,
+            Focus::Generate => Focus::Dependencies,
+            Focus::Dependencies => Focus::Form(0),
+        }
+    }
+
+    fn previous_focus(&self) -> Focus {
+        match self.focus {
+            Focus::Form(0) => Focus::Dependencies,
+            Focus::Form(index) => Focus::Form(index - 1),
+            Focus::Generate => Focus::Form(FORM_FIELDS.len() - 1),
+            Focus::Dependencies => Focus::Generate,
+        }
+    }
+}
+
+fn choice_index(choices: &MetadataValue, field: Field, value: &str) -> Option<usize> {
+    choices.values.iter().position(|choice| match field {
+        Field::BootVersion => normalize_boot_version(&choice.id) == value,
+        _ => choice.id == value,
+    })
+}
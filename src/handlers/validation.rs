@@ -0,0 +1,198 @@
+use anyhow::{anyhow, Result};
+
+/// Reserved words and literals that cannot be used as a package segment.
+const JAVA_KEYWORDS: &[&str] = &[
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const",
+    "continue", "default", "do", "double", "else", "enum", "extends", "false", "final", "finally",
+    "float", "for", "goto", "if", "implements", "import", "instanceof", "int", "interface", "long",
+    "native", "new", "null", "package", "private", "protected", "public", "return", "short", "static",
+    "strictfp", "super", "switch", "synchronized", "this", "throw", "throws", "transient", "true", "try",
+    "void", "volatile", "while", "_",
+];
+
+/// A group id is used as the base package, so it has to be a valid Java package name.
+/// Uppercase letters are legal there, only the suggestions are lowercased by convention.
+pub fn validate_group_id(group_id: &str) -> Result<()> {
+    validate_java_package("Group ID", group_id)
+}
+
+/// Maven convention: lowercase letters, digits and `-`, `_` or `.` in between.
+pub fn validate_artifact_id(artifact_id: &str) -> Result<()> {
+    let problem = if artifact_id.is_empty() {
+        Some("cannot be empty".to_string())
+    } else if artifact_id.chars().any(char::is_whitespace) {
+        Some("cannot contain spaces".to_string())
+    } else if artifact_id.chars().any(|character| character.is_ascii_uppercase()) {
+        Some("should be lowercase".to_string())
+    } else if let Some(invalid) = artifact_id.chars().find(|character| !is_artifact_char(*character)) {
+        Some(format!("contains '{}', only lowercase letters, digits, '-', '_' and '.' are allowed", invalid))
+    } else if !artifact_id.starts_with(|character: char| character.is_ascii_alphanumeric())
+        || !artifact_id.ends_with(|character: char| character.is_ascii_alphanumeric())
+    {
+        Some("has to start and end with a letter or digit".to_string())
+    } else {
+        None
+    };
+
+    match problem {
+        Some(problem) => Err(invalid("Artifact ID", artifact_id, &problem, &normalize_artifact_id(artifact_id))),
+        None => Ok(()),
+    }
+}
+
+pub fn validate_package_name(package_name: &str) -> Result<()> {
+    validate_java_package("Package name", package_name)
+}
+
+/// The package name the prompts suggest: the group id and the artifact id joined with a dot and
+/// cleaned up into a valid package name. It is never sent as is, without a package name the
+/// Initializr derives its own.
+pub fn derive_package_name(group_id: &str, artifact_id: &str) -> String {
+    normalize_package_name(&format!("{}.{}", group_id, artifact_id))
+}
+
+/// Drops the characters a package segment cannot have and lowercases the rest. Segments that
+/// start with a digit get a leading `_`, keywords a trailing one.
+pub fn normalize_package_name(input: &str) -> String {
+    input.split('.')
+        .map(|segment| {
+            segment.chars()
+                .filter(|character| character.is_ascii_alphanumeric() || *character == '_')
+                .collect::<String>()
+                .to_ascii_lowercase()
+        })
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            if segment.starts_with(|character: char| character.is_ascii_digit()) {
+                format!("_{}", segment)
+            } else if JAVA_KEYWORDS.contains(&segment.as_str()) {
+                format!("{}_", segment)
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Lowercases the artifact id, turns every other invalid character into `-` and trims
+/// the separators at both ends.
+pub fn normalize_artifact_id(input: &str) -> String {
+    let mut normalized = String::with_capacity(input.len());
+    for character in input.trim().chars().map(|character| character.to_ascii_lowercase()) {
+        let character = if is_artifact_char(character) { character } else { '-' };
+        if character == '-' && normalized.ends_with('-') {
+            continue;
+        }
+        normalized.push(character);
+    }
+
+    normalized.trim_matches(|character: char| !character.is_ascii_alphanumeric()).to_string()
+}
+
+fn validate_java_package(field: &str, input: &str) -> Result<()> {
+    let problem = if input.is_empty() {
+        Some("cannot be empty".to_string())
+    } else if input.chars().any(char::is_whitespace) {
+        Some("cannot contain spaces".to_string())
+    } else if input.split('.').any(str::is_empty) {
+        Some("has an empty segment".to_string())
+    } else if let Some(segment) = input.split('.').find(|segment| segment.starts_with(|character: char| character.is_ascii_digit())) {
+        Some(format!("has the segment '{}', which starts with a digit", segment))
+    } else if let Some(invalid) = input.chars().find(|character| !character.is_ascii_alphanumeric() && !matches!(character, '_' | '.')) {
+        Some(format!("contains '{}', only letters, digits and '_' are allowed in a package", invalid))
+    } else {
+        input.split('.')
+            .find(|segment| JAVA_KEYWORDS.contains(segment))
+            .map(|keyword| format!("contains '{}', which is a reserved Java keyword", keyword))
+    };
+
+    match problem {
+        Some(problem) => Err(invalid(field, input, &problem, &normalize_package_name(input))),
+        None => Ok(()),
+    }
+}
+
+fn invalid(field: &str, input: &str, problem: &str, suggestion: &str) -> anyhow::Error {
+    if input.is_empty() {
+        anyhow!("{} {}", field, problem)
+    } else if suggestion.is_empty() || suggestion == input {
+        anyhow!("{} '{}' {}", field, input, problem)
+    } else {
+        anyhow!("{} '{}' {}, try '{}'", field, input, problem, suggestion)
+    }
+}
+
+fn is_artifact_char(character: char) -> bool {
+    character.is_ascii_lowercase() || character.is_ascii_digit() || matches!(character, '-' | '_' | '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(result: Result<()>) -> String {
+        result.unwrap_err().to_string()
+    }
+
+    #[test]
+    fn group_id_accepts_java_packages() {
+        for group_id in ["com.example", "com.MyCorp", "io.github.user_name", "org.example2", "a"] {
+            assert!(validate_group_id(group_id).is_ok(), "{} should be valid", group_id);
+        }
+    }
+
+    #[test]
+    fn group_id_rejects_invalid_packages_with_a_suggestion() {
+        assert_eq!(message(validate_group_id("")), "Group ID cannot be empty");
+        assert_eq!(message(validate_group_id("com.my corp")), "Group ID 'com.my corp' cannot contain spaces, try 'com.mycorp'");
+        assert_eq!(message(validate_group_id("com..example")), "Group ID 'com..example' has an empty segment, try 'com.example'");
+        assert_eq!(message(validate_group_id("com.1corp")), "Group ID 'com.1corp' has the segment '1corp', which starts with a digit, try 'com._1corp'");
+        assert_eq!(
+            message(validate_group_id("com.my-corp")),
+            "Group ID 'com.my-corp' contains '-', only letters, digits and '_' are allowed in a package, try 'com.mycorp'"
+        );
+        assert_eq!(message(validate_group_id("com.new")), "Group ID 'com.new' contains 'new', which is a reserved Java keyword, try 'com.new_'");
+    }
+
+    #[test]
+    fn package_name_allows_uppercase() {
+        assert!(validate_package_name("com.MyCorp.api").is_ok());
+        assert!(validate_package_name("com.mycorp.userservice").is_ok());
+        assert_eq!(message(validate_package_name("com.MyCorp.")), "Package name 'com.MyCorp.' has an empty segment, try 'com.mycorp'");
+    }
+
+    #[test]
+    fn artifact_id_follows_the_maven_convention() {
+        for artifact_id in ["demo", "user-service", "user_service", "api.v2", "9lives"] {
+            assert!(validate_artifact_id(artifact_id).is_ok(), "{} should be valid", artifact_id);
+        }
+
+        assert_eq!(message(validate_artifact_id("")), "Artifact ID cannot be empty");
+        assert_eq!(message(validate_artifact_id("User Service")), "Artifact ID 'User Service' cannot contain spaces, try 'user-service'");
+        assert_eq!(message(validate_artifact_id("UserService")), "Artifact ID 'UserService' should be lowercase, try 'userservice'");
+        assert_eq!(
+            message(validate_artifact_id("user@service")),
+            "Artifact ID 'user@service' contains '@', only lowercase letters, digits, '-', '_' and '.' are allowed, try 'user-service'"
+        );
+        assert_eq!(message(validate_artifact_id("-demo-")), "Artifact ID '-demo-' has to start and end with a letter or digit, try 'demo'");
+    }
+
+    #[test]
+    fn derives_a_valid_package_suggestion() {
+        assert_eq!(derive_package_name("com.example", "demo"), "com.example.demo");
+        assert_eq!(derive_package_name("com.mycorp", "user-service"), "com.mycorp.userservice");
+        assert_eq!(derive_package_name("com.MyCorp", "api"), "com.mycorp.api");
+        assert_eq!(derive_package_name("org.example", "2fa"), "org.example._2fa");
+        assert_eq!(derive_package_name("org.example", "switch"), "org.example.switch_");
+        assert_eq!(derive_package_name("com.example", "api.v2"), "com.example.api.v2");
+        assert!(validate_package_name(&derive_package_name("com.my-corp", "User Service!")).is_ok());
+    }
+
+    #[test]
+    fn normalizes_artifact_ids() {
+        assert_eq!(normalize_artifact_id("  My  Cool App!  "), "my-cool-app");
+        assert_eq!(normalize_artifact_id("already-fine"), "already-fine");
+        assert_eq!(normalize_artifact_id("--"), "");
+    }
+}
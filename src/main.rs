@@ -1,14 +1,26 @@
 use std::env::current_dir;
+use std::time::Duration;
 use std::io::{stdout, BufWriter, Write};
 use clap::Parser;
 use anyhow::{anyhow, Context, Result};
-use xspring::cli::commands::Commands;
+use xspring::cli::commands::{config::ConfigCommand, preset::PresetCommand, Commands};
 use xspring::cli::root::Cli;
-use xspring::client::spring_initializr::generate_project;
+use xspring::client::spring_initializr::{SpringInitializr, DEFAULT_SERVER};
+use xspring::handlers::add::add_dependencies;
+use xspring::handlers::answers::load_answers;
+use xspring::handlers::config::{edit_config, get_config_value, load_config, print_config, set_config_value, unset_config_value};
+use xspring::handlers::generate::{generate, GenerateOptions};
+use xspring::handlers::git::{GitOptions, DEFAULT_COMMIT_MESSAGE};
 use xspring::handlers::interactive::{pure_interactivity, quick_interactivity};
 use tracing::debug;
 use tracing_appender::rolling;
-use xspring::handlers::list::{get_lists, print_categories, print_values};
+use xspring::handlers::non_interactive::non_interactivity;
+use xspring::handlers::preset::{delete_preset, list_presets, load_preset, preset_dirs, print_preset, print_presets, save_preset};
+use xspring::handlers::search::{print_search_results, search_dependencies};
+use xspring::handlers::share_url::{parse_share_url, share_url};
+use xspring::handlers::tui::tui_interactivity;
+use xspring::handlers::list::{get_lists, print_list};
+use xspring::models::answers::Answers;
 use xspring::models::list::Lists;
 
 #[tokio::main(flavor = "current_thread")]
@@ -23,8 +35,75 @@ async fn main() -> Result<()> {
         .with_writer(logfile)
         .with_ansi(true)
         .init();
+    let policy = cli.overwrite_policy()?;
     let out_dir = cli.output.unwrap_or(current_dir()?);
 
+    // Handled before the config is loaded, so that a broken config file can still be fixed
+    if let Some(Commands::Config { command }) = cli.command {
+        let mut buf = BufWriter::new(stdout().lock());
+        match command {
+            ConfigCommand::Get { key } => get_config_value(&mut buf, &key)?,
+            ConfigCommand::Set { key, value } => set_config_value(&key, &value)
+                .with_context(|| format!("Failed to set '{}'", key))?,
+            ConfigCommand::Unset { key } => unset_config_value(&key)
+                .with_context(|| format!("Failed to unset '{}'", key))?,
+            ConfigCommand::List => print_config(&mut buf, cli.format)
+                .with_context(|| "Failed to print the config")?,
+            ConfigCommand::Edit => edit_config()
+                .with_context(|| "Failed to edit the config file")?,
+        }
+
+        return Ok(());
+    }
+
+    let config = load_config()
+        .with_context(|| "Failed to load the xspring config file")?;
+    let explicit_server = cli.server.is_some();
+    let server = cli.server
+        .or(config.server)
+        .unwrap_or_else(|| DEFAULT_SERVER.to_string());
+    debug!("Using Spring Initializr server: {}", server);
+    let mut initializr = SpringInitializr::new(server).with_offline(cli.offline);
+    if let Some(cache_ttl_secs) = config.cache_ttl_secs {
+        initializr = initializr.with_cache_ttl(Duration::from_secs(cache_ttl_secs));
+    }
+
+    let answers = match cli.from.as_deref() {
+        Some(path) => load_answers(path)?,
+        None => Answers::default(),
+    };
+    let format = cli.format;
+    let init_git = !cli.no_git && (cli.git || config.git.init || cli.git_remote.is_some());
+    let git = init_git.then(|| GitOptions {
+        remote: cli.git_remote,
+        commit_message: cli.commit_message
+            .or(config.git.commit_message)
+            .unwrap_or_else(|| DEFAULT_COMMIT_MESSAGE.to_string()),
+        ignore: config.git.ignore,
+    });
+    let options = GenerateOptions {
+        out_dir,
+        policy,
+        mode: cli.output_mode,
+        save_answers_path: cli.save_answers,
+        dry_run: cli.dry_run,
+        format,
+        git,
+        post_generate_hooks: if cli.no_hooks { Vec::new() } else { config.hooks.post_generate },
+    };
+    let preset_dirs = preset_dirs(&config.preset_paths)?;
+    let (answers, defaults) = match cli.preset.as_deref() {
+        Some(name) => {
+            let preset = load_preset(name, &preset_dirs)?;
+            if cli.preset_as_defaults {
+                (answers, preset.or(config.defaults))
+            } else {
+                (answers.or(preset), config.defaults)
+            }
+        }
+        None => (answers, config.defaults),
+    };
+
     if let Some(command) = cli.command {
 
         let stdout = stdout();
@@ -32,57 +111,117 @@ async fn main() -> Result<()> {
         let mut buf = BufWriter::new(handle);
 
         match command {
-           Commands::Quick {maven, extended, deps} => {
-               let query_params = quick_interactivity(maven, extended, deps).await
+           Commands::Quick {maven, extended, deps, review} => {
+               let query_params = quick_interactivity(&initializr, answers, &defaults, maven, extended, deps, review).await
                    .with_context(|| "Failed to run quick interactivity")?;
-               generate_project(query_params, out_dir).await
-                   .with_context(|| "Failed to generate a spring boot project")?;
+               generate(&initializr, query_params, &options).await?;
+           }
+
+           Commands::Tui => {
+               let query_params = tui_interactivity(&initializr, answers, &defaults).await
+                   .with_context(|| "Failed to run the TUI")?;
+               generate(&initializr, query_params, &options).await?;
+           }
+
+           Commands::New(args) => {
+               let query_params = non_interactivity(&initializr, args.into_answers().or(answers).or(defaults)).await
+                   .with_context(|| "Failed to resolve the project options")?;
+               generate(&initializr, query_params, &options).await?;
+           }
+
+           Commands::Add { ids, project_dir, boot_version } => {
+               let project_dir = match project_dir {
+                   Some(project_dir) => project_dir,
+                   None => current_dir()?,
+               };
+               add_dependencies(&initializr, &project_dir, &ids, boot_version).await
+                   .with_context(|| "Failed to add dependencies to the project")?;
+           }
+
+           Commands::ImportUrl { url } => {
+               let (url_server, url_answers) = parse_share_url(&url)
+                   .with_context(|| "Failed to parse the share link")?;
+               let initializr = if explicit_server {
+                   initializr
+               } else {
+                   initializr.with_server(url_server)
+               };
+               let query_params = non_interactivity(&initializr, url_answers.or(answers).or(defaults)).await
+                   .with_context(|| "Failed to resolve the project options from the share link")?;
+               generate(&initializr, query_params, &options).await?;
+           }
+
+           Commands::Preset { command } => match command {
+               PresetCommand::Save { preset_name, overwrite, project } => save_preset(&preset_name, &project.into_answers().or(answers), overwrite, &preset_dirs)?,
+               PresetCommand::List => {
+                   let presets = list_presets(&preset_dirs)?;
+                   print_presets(&mut buf, &presets, format)
+                       .with_context(|| "Failed to print the presets")?;
+               }
+               PresetCommand::Show { name } => {
+                   let preset = load_preset(&name, &preset_dirs)?;
+                   print_preset(&mut buf, &preset, format)
+                       .with_context(|| "Failed to print the preset")?;
+               }
+               PresetCommand::Delete { name } => delete_preset(&name, &preset_dirs)?,
+           },
+
+           Commands::ExportUrl { file } => {
+               let file_answers = load_answers(&file)?;
+               writeln!(&mut buf, "{}", share_url(initializr.server(), &file_answers))?;
+           }
+
+           Commands::Search { term, category, limit } => {
+               let categories = get_lists(&initializr, "deps").await
+                   .with_context(|| "Failed to get dependencies")?;
+               let Lists::Categories(categories) = categories else {
+                   return Err(anyhow!("Expected dependency categories from the metadata"));
+               };
+
+               let mut results = search_dependencies(categories, &term, category.as_deref())?;
+               results.truncate(limit);
+               print_search_results(&mut buf, &results, format)
+                   .with_context(|| "Failed to print the search results")?;
            }
 
            Commands::List { java: true, .. } => {
-                let java_versions: Lists = get_lists("java").await
+                let java_versions: Lists = get_lists(&initializr, "java").await
                     .with_context(|| "Failed to get java versions")?;  
-                writeln!(&mut buf, "Available Java Versions:")?;
 
-                print_values(&mut buf, java_versions)
+                print_list(&mut buf, "Available Java Versions:", java_versions, format, false)
                  .with_context(|| "Failed to print java versions")?;
                 
            }
 
            Commands::List { boot: true, .. } => {
-               let boot_versions: Lists = get_lists("boot").await
+               let boot_versions: Lists = get_lists(&initializr, "boot").await
                    .with_context(|| "Failed to get boot version")?;
-               writeln!(&mut buf, "Available Spring Boot Versions:")?;
 
-               print_values(&mut buf, boot_versions)
+               print_list(&mut buf, "Available Spring Boot Versions:", boot_versions, format, false)
                    .with_context(|| "Failed to print boot versions")?;
            }
 
            Commands::List { project_type: true, .. } => {
-               let project_types: Lists = get_lists("project_type").await
+               let project_types: Lists = get_lists(&initializr, "project_type").await
                    .with_context(|| "Failed to get project types")?;
-               writeln!(&mut buf, "Available Project Types:")?;
 
-               print_values(&mut buf, project_types)
+               print_list(&mut buf, "Available Project Types:", project_types, format, false)
                    .with_context(|| "Failed to print project types")?;
            }
 
            Commands::List { language: true, .. } => {
-               let languages = get_lists("language").await
+               let languages = get_lists(&initializr, "language").await
                    .with_context(|| "Failed to get languages")?;
-               writeln!(&mut buf, "Available Languages:")?;
 
-               print_values(&mut buf, languages)
+               print_list(&mut buf, "Available Languages:", languages, format, false)
                    .with_context(|| "Failed to print languages")?;
            }
 
-           Commands::List { deps: true, ..  } => {
-               let categories = get_lists("deps").await
+           Commands::List { deps: true, details, ..  } => {
+               let categories = get_lists(&initializr, "deps").await
                    .with_context(|| "Failed to get dependencies")?;
-               writeln!(&mut buf, "Available Dependencies:-")?;
-               writeln!(&mut buf, " ")?;
 
-               print_categories(&mut buf, categories)
+               print_list(&mut buf, "Available Dependencies:-", categories, format, details)
                    .with_context(|| "Failed to print Dependencies with Categories")?;
            }
 
@@ -92,10 +231,9 @@ async fn main() -> Result<()> {
         }
 
     } else {
-        let query_params = pure_interactivity().await
+        let query_params = pure_interactivity(&initializr, answers, &defaults).await
             .with_context(|| "Failed to run pure interactivity")?;
-        generate_project(query_params, out_dir).await
-            .with_context(|| "Failed to generate a spring boot project")?;
+        generate(&initializr, query_params, &options).await?;
     }
 
     
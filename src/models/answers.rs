@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Answers {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub project_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boot_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifact_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packaging: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub java_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_dir: Option<String>,
+}
+
+impl Answers {
+    pub fn is_empty(&self) -> bool {
+        [
+            &self.project_type, &self.language, &self.boot_version, &self.group_id, &self.artifact_id, &self.name,
+            &self.description, &self.package_name, &self.packaging, &self.java_version, &self.dependencies, &self.base_dir,
+        ]
+            .iter()
+            .all(|value| value.is_none())
+    }
+
+    /// Fills every field missing in `self` from `fallback`.
+    ///
+    /// The base dir follows the artifact id and the package name follows the group and
+    /// artifact ids, so overriding only those does not keep the folder or package of the old ones.
+    pub fn or(self, fallback: Answers) -> Answers {
+        let base_dir = match (&self.base_dir, &self.artifact_id) {
+            (Some(_), _) => self.base_dir,
+            (None, Some(_)) => None,
+            (None, None) => fallback.base_dir,
+        };
+        let package_name = match (&self.package_name, self.group_id.is_some() || self.artifact_id.is_some()) {
+            (Some(_), _) => self.package_name,
+            (None, true) => None,
+            (None, false) => fallback.package_name,
+        };
+
+        Answers {
+            project_type: self.project_type.or(fallback.project_type),
+            language: self.language.or(fallback.language),
+            boot_version: self.boot_version.or(fallback.boot_version),
+            group_id: self.group_id.or(fallback.group_id),
+            artifact_id: self.artifact_id.or(fallback.artifact_id),
+            name: self.name.or(fallback.name),
+            description: self.description.or(fallback.description),
+            package_name,
+            packaging: self.packaging.or(fallback.packaging),
+            java_version: self.java_version.or(fallback.java_version),
+            dependencies: self.dependencies.or(fallback.dependencies),
+            base_dir,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(group_id: &str, artifact_id: &str) -> Answers {
+        Answers {
+            group_id: Some(group_id.to_string()),
+            artifact_id: Some(artifact_id.to_string()),
+            package_name: Some(format!("{}.{}", group_id, artifact_id)),
+            base_dir: Some(artifact_id.to_string()),
+            java_version: Some("21".to_string()),
+            ..Answers::default()
+        }
+    }
+
+    #[test]
+    fn or_fills_the_missing_fields() {
+        let merged = Answers { java_version: Some("17".to_string()), ..Answers::default() }.or(answers("com.example", "demo"));
+
+        assert_eq!(merged.java_version.as_deref(), Some("17"));
+        assert_eq!(merged.group_id.as_deref(), Some("com.example"));
+        assert_eq!(merged.package_name.as_deref(), Some("com.example.demo"));
+        assert_eq!(merged.base_dir.as_deref(), Some("demo"));
+    }
+
+    #[test]
+    fn or_drops_the_base_dir_and_package_of_overridden_ids() {
+        let artifact = Answers { artifact_id: Some("api".to_string()), ..Answers::default() }.or(answers("com.example", "demo"));
+        assert_eq!(artifact.artifact_id.as_deref(), Some("api"));
+        assert_eq!(artifact.base_dir, None);
+        assert_eq!(artifact.package_name, None);
+
+        let group = Answers { group_id: Some("org.acme".to_string()), ..Answers::default() }.or(answers("com.example", "demo"));
+        assert_eq!(group.group_id.as_deref(), Some("org.acme"));
+        assert_eq!(group.base_dir.as_deref(), Some("demo"));
+        assert_eq!(group.package_name, None);
+    }
+
+    #[test]
+    fn or_keeps_explicit_base_dir_and_package() {
+        let explicit = Answers {
+            artifact_id: Some("api".to_string()),
+            package_name: Some("org.acme.api".to_string()),
+            base_dir: Some("services/api".to_string()),
+            ..Answers::default()
+        };
+
+        let merged = explicit.or(answers("com.example", "demo"));
+        assert_eq!(merged.package_name.as_deref(), Some("org.acme.api"));
+        assert_eq!(merged.base_dir.as_deref(), Some("services/api"));
+    }
+
+    #[test]
+    fn is_empty_only_without_any_value() {
+        assert!(Answers::default().is_empty());
+        assert!(!Answers { base_dir: Some("demo".to_string()), ..Answers::default() }.is_empty());
+    }
+}
@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use crate::models::answers::Answers;
+
+/// Keys are camelCase like the answers keys of `defaults`, the snake_case aliases keep older files loading.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Config {
+    pub server: Option<String>,
+    #[serde(alias = "cache_ttl_secs")]
+    pub cache_ttl_secs: Option<u64>,
+    /// Extra preset directories, e.g. a checked-out team repository. Relative paths start at the config file
+    #[serde(alias = "preset_paths")]
+    pub preset_paths: Vec<PathBuf>,
+    pub git: GitConfig,
+    pub hooks: HooksConfig,
+    /// Prefills the prompts and fills in what `new` is not given, using the answers file keys
+    pub defaults: Answers,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GitConfig {
+    /// Initialize a repository after every generation, as if `--git` was passed
+    pub init: bool,
+    #[serde(alias = "commit_message")]
+    pub commit_message: Option<String>,
+    /// Extra .gitignore entries added to every generated project
+    pub ignore: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HooksConfig {
+    /// Shell commands run in the generated project directory, in order
+    #[serde(alias = "post_generate")]
+    pub post_generate: Vec<String>,
+}
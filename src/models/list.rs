@@ -1,6 +1,9 @@
-use crate::models::spring::{Value, DependencyCategories};
+use serde::Serialize;
+use crate::models::spring::{MetadataValue, DependencyCategories};
 
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
 pub enum Lists {
-    Values(Vec<Value>),
+    Values(MetadataValue),
     Categories(Vec<DependencyCategories>),
 }
@@ -1,2 +1,5 @@
 pub mod spring;
 pub mod list;
+pub mod config;
+pub mod answers;
+pub mod version;
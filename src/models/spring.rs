@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
+use crate::models::version::{Version, VersionRange};
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -11,11 +12,13 @@ pub struct InitializrMetadata {
     pub packaging: MetadataValue,
     #[serde(rename = "type")]
     pub project_type: MetadataValue,
+    pub group_id: DefaultText,
+    pub artifact_id: DefaultText,
     pub name: DefaultText,
     pub description: DefaultText,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryParam {
     #[serde(rename = "type")]
@@ -26,6 +29,9 @@ pub struct QueryParam {
     pub artifact_id: String,
     pub name: String,
     pub description: String,
+    /// Only sent when one was given, the Initializr derives it from the ids otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package_name: Option<String>,
     pub packaging: String,
     pub java_version: String,
     pub dependencies: String,
@@ -38,14 +44,14 @@ pub struct Dependencies {
     pub values: Vec<DependencyCategories>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct DependencyCategories {
     pub name: String,
     pub values: Vec<Value>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MetadataValue {
     pub default: String,
@@ -57,11 +63,58 @@ pub struct DefaultText {
     pub default: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Value {
     pub id: String,
     pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_range: Option<String>,
+    #[serde(rename = "_links", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub links: BTreeMap<String, Links>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum Links {
+    One(Link),
+    Many(Vec<Link>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Link {
+    pub href: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub templated: bool,
+}
+
+impl Value {
+    pub fn version_range(&self) -> Option<VersionRange> {
+        self.version_range.as_deref().and_then(|range| range.parse().ok())
+    }
+
+    /// Flattens the `_links` relations into `(relation, link)` pairs, e.g. `("guide", ..)`.
+    pub fn links(&self) -> Vec<(&str, &Link)> {
+        self.links.iter()
+            .flat_map(|(rel, links)| {
+                let links = match links {
+                    Links::One(link) => std::slice::from_ref(link),
+                    Links::Many(links) => links.as_slice(),
+                };
+                links.iter().map(move |link| (rel.as_str(), link))
+            })
+            .collect()
+    }
+
+    /// Values without a (parsable) version range are compatible with every Spring Boot version.
+    pub fn is_compatible_with(&self, boot_version: &Version) -> bool {
+        self.version_range().is_none_or(|range| range.contains(boot_version))
+    }
 }
 
 impl fmt::Display for Value {
@@ -69,3 +122,53 @@ impl fmt::Display for Value {
         write!(f, "{}", self.name)
     }
 }
+
+pub fn normalize_boot_version(version: &str) -> String {
+    version
+        .replace(".RELEASE", "")
+        .replace(".BUILD-SNAPSHOT", "-SNAPSHOT")
+        .replace(".M", "-M")
+        .replace(".RC", "-RC")
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyDetails {
+    pub boot_version: String,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, Coordinates>,
+    #[serde(default)]
+    pub repositories: BTreeMap<String, Repository>,
+    #[serde(default)]
+    pub boms: BTreeMap<String, Bom>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Coordinates {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: Option<String>,
+    pub scope: Option<String>,
+    pub bom: Option<String>,
+    pub repository: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Repository {
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub snapshot_enabled: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Bom {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: String,
+    #[serde(default)]
+    pub repositories: Vec<String>,
+}
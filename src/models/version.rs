@@ -0,0 +1,253 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+use anyhow::{anyhow, Context, Error, Result};
+use crate::models::spring::normalize_boot_version;
+
+/// Spring Boot style version, e.g. `3.5.6`, `4.0.0-M1`, `3.4.0.RELEASE` or `3.5.7-SNAPSHOT`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub qualifier: Qualifier,
+}
+
+/// Declared in ascending order, so milestones sort before release candidates,
+/// snapshots and finally the release itself, the same way the Initializr orders them.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Qualifier {
+    Milestone(u64),
+    ReleaseCandidate(u64),
+    Snapshot,
+    Release,
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let normalized = normalize_boot_version(input.trim());
+        let (numbers, qualifier) = match normalized.split_once('-') {
+            Some((numbers, qualifier)) => (numbers, Some(qualifier)),
+            None => (normalized.as_str(), None),
+        };
+
+        let mut parts = numbers.split('.').map(|part| {
+            part.parse::<u64>()
+                .with_context(|| format!("Invalid version '{}'", input))
+        });
+        let major = parts.next().ok_or_else(|| anyhow!("Invalid version '{}'", input))??;
+        let minor = parts.next().transpose()?.unwrap_or(0);
+        let patch = parts.next().transpose()?.unwrap_or(0);
+        if parts.next().is_some() {
+            return Err(anyhow!("Invalid version '{}'", input));
+        }
+
+        let qualifier = match qualifier {
+            None => Qualifier::Release,
+            Some("SNAPSHOT") => Qualifier::Snapshot,
+            Some(qualifier) => {
+                if let Some(number) = qualifier.strip_prefix("RC") {
+                    Qualifier::ReleaseCandidate(number.parse().with_context(|| format!("Invalid version qualifier in '{}'", input))?)
+                } else if let Some(number) = qualifier.strip_prefix('M') {
+                    Qualifier::Milestone(number.parse().with_context(|| format!("Invalid version qualifier in '{}'", input))?)
+                } else {
+                    return Err(anyhow!("Unknown version qualifier '{}' in '{}'", qualifier, input));
+                }
+            }
+        };
+
+        Ok(Version { major, minor, patch, qualifier })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        match self.qualifier {
+            Qualifier::Milestone(number) => write!(f, "-M{}", number),
+            Qualifier::ReleaseCandidate(number) => write!(f, "-RC{}", number),
+            Qualifier::Snapshot => write!(f, "-SNAPSHOT"),
+            Qualifier::Release => Ok(()),
+        }
+    }
+}
+
+/// Maven style version range as used by the Initializr `versionRange` metadata,
+/// e.g. `[3.4.0,4.0.0-M1)`. A bare version such as `3.4.0` means "3.4.0 or later".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+    pub lower: Version,
+    pub lower_inclusive: bool,
+    pub upper: Option<Version>,
+    pub upper_inclusive: bool,
+}
+
+impl VersionRange {
+    pub fn contains(&self, version: &Version) -> bool {
+        let above_lower = match version.cmp(&self.lower) {
+            Ordering::Greater => true,
+            Ordering::Equal => self.lower_inclusive,
+            Ordering::Less => false,
+        };
+        let below_upper = match &self.upper {
+            None => true,
+            Some(upper) => match version.cmp(upper) {
+                Ordering::Less => true,
+                Ordering::Equal => self.upper_inclusive,
+                Ordering::Greater => false,
+            },
+        };
+
+        above_lower && below_upper
+    }
+}
+
+impl FromStr for VersionRange {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let lower_inclusive = match input.chars().next() {
+            Some('[') => true,
+            Some('(') => false,
+            _ => {
+                return Ok(VersionRange {
+                    lower: input.parse()?,
+                    lower_inclusive: true,
+                    upper: None,
+                    upper_inclusive: false,
+                });
+            }
+        };
+        let upper_inclusive = match input.chars().last() {
+            Some(']') => true,
+            Some(')') => false,
+            _ => return Err(anyhow!("Unterminated version range '{}'", input)),
+        };
+
+        let (lower, upper) = input[1..input.len() - 1]
+            .split_once(',')
+            .ok_or_else(|| anyhow!("Version range '{}' must have a lower and an upper bound", input))?;
+        let upper = upper.trim();
+
+        Ok(VersionRange {
+            lower: lower.parse()?,
+            lower_inclusive,
+            upper: if upper.is_empty() { None } else { Some(upper.parse()?) },
+            upper_inclusive,
+        })
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lower_op = if self.lower_inclusive { ">=" } else { ">" };
+        write!(f, "{}{}", lower_op, self.lower)?;
+        if let Some(upper) = &self.upper {
+            let upper_op = if self.upper_inclusive { "<=" } else { "<" };
+            write!(f, " and {}{}", upper_op, upper)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(input: &str) -> Version {
+        input.parse().unwrap()
+    }
+
+    fn range(input: &str) -> VersionRange {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn qualifiers_sort_before_the_release() {
+        let ordered = ["3.5.0-M1", "3.5.0-M2", "3.5.0-RC1", "3.5.0-RC2", "3.5.0-SNAPSHOT", "3.5.0", "3.5.1-M1", "3.5.1", "3.10.0"];
+
+        for pair in ordered.windows(2) {
+            assert!(version(pair[0]) < version(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn parses_short_and_qualified_versions() {
+        assert_eq!(version("3.5"), Version { major: 3, minor: 5, patch: 0, qualifier: Qualifier::Release });
+        assert_eq!(version(" 4.0.0-RC1 ").qualifier, Qualifier::ReleaseCandidate(1));
+        assert_eq!(version("4.0.0-M3").qualifier, Qualifier::Milestone(3));
+        assert_eq!(version("3.5.7-SNAPSHOT").qualifier, Qualifier::Snapshot);
+    }
+
+    #[test]
+    fn normalizes_the_old_qualifier_style() {
+        assert_eq!(version("2.7.0.RELEASE"), version("2.7.0"));
+        assert_eq!(version("2.7.1.BUILD-SNAPSHOT"), version("2.7.1-SNAPSHOT"));
+        assert_eq!(version("3.0.0.M1"), version("3.0.0-M1"));
+        assert_eq!(version("3.0.0.RC2"), version("3.0.0-RC2"));
+    }
+
+    #[test]
+    fn rejects_invalid_versions() {
+        for input in ["", "abc", "3.x.0", "1.2.3.4", "3.5.0-beta", "3.5.0-RCx"] {
+            assert!(input.parse::<Version>().is_err(), "{:?} should not parse", input);
+        }
+    }
+
+    #[test]
+    fn displays_the_normalized_version() {
+        assert_eq!(version("3.0.0.M1").to_string(), "3.0.0-M1");
+        assert_eq!(version("2.7.0.RELEASE").to_string(), "2.7.0");
+        assert_eq!(version("3.5").to_string(), "3.5.0");
+    }
+
+    #[test]
+    fn range_bounds_are_inclusive_or_exclusive() {
+        let half_open = range("[3.4.0,4.0.0-M1)");
+        assert!(half_open.contains(&version("3.4.0")));
+        assert!(half_open.contains(&version("3.5.6")));
+        assert!(!half_open.contains(&version("3.4.0-RC1")));
+        assert!(!half_open.contains(&version("4.0.0-M1")));
+        assert!(!half_open.contains(&version("4.0.0")));
+
+        let closed = range("(3.4.0,3.5.0]");
+        assert!(!closed.contains(&version("3.4.0")));
+        assert!(closed.contains(&version("3.4.1")));
+        assert!(closed.contains(&version("3.5.0")));
+        assert!(!closed.contains(&version("3.5.1")));
+    }
+
+    #[test]
+    fn range_upper_bound_can_be_open() {
+        let open = range("[3.4.0,)");
+        assert_eq!(open.upper, None);
+        assert!(open.contains(&version("99.0.0")));
+        assert!(!open.contains(&version("3.3.9")));
+    }
+
+    #[test]
+    fn bare_version_means_that_version_or_later() {
+        let bare = range("3.4.0");
+        assert_eq!(bare, range("[3.4.0,)"));
+        assert!(bare.contains(&version("3.4.0")));
+        assert!(bare.contains(&version("4.0.0-M1")));
+        assert!(!bare.contains(&version("3.4.0-SNAPSHOT")));
+    }
+
+    #[test]
+    fn range_normalizes_old_qualifiers() {
+        let legacy = range("[2.7.0.RELEASE,3.0.0.M1)");
+        assert!(legacy.contains(&version("2.7.18")));
+        assert!(!legacy.contains(&version("3.0.0-M1")));
+        assert_eq!(legacy.to_string(), ">=2.7.0 and <3.0.0-M1");
+    }
+
+    #[test]
+    fn rejects_invalid_ranges() {
+        for input in ["[3.4.0,4.0.0", "[3.4.0]", "[abc,4.0.0)", ""] {
+            assert!(input.parse::<VersionRange>().is_err(), "{:?} should not parse", input);
+        }
+    }
+}